@@ -0,0 +1,127 @@
+use log::{info, warn};
+
+use f1scraper::history::CircuitHistory;
+
+use crate::commands::{fastestlap, race, ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The circuit or Grand Prix name (e.g. `monaco`, `Great Britain`, `silverstone`)
+    circuit_name: String,
+
+    /// Also match on the venue, which requires scraping every race result page
+    #[arg(long)]
+    venue: bool,
+
+    /// Do not scrape qualifying results for the pole sitters
+    #[arg(long)]
+    no_qualifying: bool,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    let scraper = &scrape_ctx.scraper;
+
+    let mut history = CircuitHistory::new(&args.circuit_name);
+    for year in year_min..=year_max {
+        let summary = scrape_ctx.attempt(year, "race summary", || {
+            race::summary::query_and_parse(scraper, year)
+        });
+        let Some(summary) = summary? else {
+            continue;
+        };
+
+        // the races of the season, with their venue when matching on it
+        let mut races = vec![];
+        for gp in &summary.data {
+            let circuit = gp.circuit().with_context(|| {
+                format!(
+                    "obtain circuit infos from summary data (circuit: `{}`)",
+                    gp.grand_prix
+                )
+            })?;
+
+            let mut venue = None;
+            if args.venue {
+                let target = format!("race result {}", circuit.display_name);
+                let race_result = scrape_ctx.attempt(year, &target, || {
+                    race::result::query_and_parse(scraper, year, &circuit)
+                });
+                let Some(race_result) = race_result? else {
+                    continue;
+                };
+                venue = race_result.venue;
+            }
+            races.push((gp, circuit, venue));
+        }
+        let candidates: Vec<_> = races
+            .iter()
+            .map(|(_, circuit, venue)| (circuit, venue.as_ref()))
+            .collect();
+
+        let mut fastest_laps = None;
+        for idx in history.matching(&candidates) {
+            let (gp, circuit, venue) = &races[idx];
+            info!("[{}] found race: {:?}", year, circuit);
+
+            let mut qualifying = None;
+            if !args.no_qualifying {
+                qualifying = race::qualifying::query_and_parse(scraper, year, circuit)
+                    .map_err(|err| {
+                        warn!(
+                            "[{}][{}] qualifying not available: {:#}",
+                            year, circuit.display_name, err
+                        )
+                    })
+                    .ok();
+            }
+
+            // only scraped once per season, for seasons with a match, the rows
+            // have no fastest lap when it failed
+            if fastest_laps.is_none() {
+                fastest_laps = Some(scrape_ctx.attempt(year, "fastest lap summary", || {
+                    fastestlap::summary::query_and_parse(scraper, year)
+                })?);
+            }
+
+            // a Grand Prix name can appear more than once in a season
+            let fastest_lap = fastest_laps
+                .as_ref()
+                .and_then(Option::as_ref)
+                .and_then(|fl| fl.for_round(idx, &gp.grand_prix));
+            history.add_race(
+                year,
+                gp,
+                circuit,
+                venue.as_ref(),
+                qualifying.as_ref(),
+                fastest_lap,
+            );
+        }
+    }
+
+    if history.rows.is_empty() {
+        return Err(anyhow::anyhow!(
+            "find circuit between `{}` and `{}` with name: {}",
+            year_min,
+            year_max,
+            args.circuit_name
+        ));
+    }
+    print(&history)
+}
+
+fn print(history: &CircuitHistory) -> Result<()> {
+    for row in history.rows.iter() {
+        let prefix = format!(
+            "[{}][{} ({})]",
+            row.year, row.circuit.display_name, row.circuit.name
+        );
+        println!("{prefix} {row:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use super::ScrapeContext;
+
+mod history;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Commands {
+    /// List the winners, pole sitters and fastest laps at a circuit across seasons
+    History(history::Args),
+}
+
+pub fn run(scrape_ctx: ScrapeContext, cmd: Commands) -> Result<()> {
+    match cmd {
+        Commands::History(args) => history::run(scrape_ctx, args),
+    }
+}
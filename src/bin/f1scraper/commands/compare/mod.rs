@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use super::ScrapeContext;
+
+mod teammates;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Commands {
+    /// Compare teammates head-to-head
+    Teammates(teammates::Args),
+}
+
+pub fn run(scrape_ctx: ScrapeContext, cmd: Commands) -> Result<()> {
+    match cmd {
+        Commands::Teammates(args) => teammates::run(scrape_ctx, args),
+    }
+}
@@ -0,0 +1,47 @@
+use f1scraper::compare::TeammateReport;
+
+use crate::commands::{race, ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Do not scrape qualifying results
+    #[arg(long)]
+    no_qualifying: bool,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    for year in year_min..=year_max {
+        let races = scrape_ctx.attempt(year, "race results", || {
+            race::result::query_season(&scrape_ctx.scraper, year)
+        });
+        let Some(races) = races? else {
+            continue;
+        };
+
+        // qualifying results are not published for every season
+        let mut qualifying = vec![];
+        if !args.no_qualifying {
+            let circuits: Vec<_> = races.iter().map(|r| r.circuit.clone()).collect();
+            qualifying = race::qualifying::query_available(&scrape_ctx.scraper, year, &circuits);
+        }
+
+        let report = TeammateReport::build(&races, &qualifying);
+        print(&report)?
+    }
+    Ok(())
+}
+
+fn print(report: &TeammateReport) -> Result<()> {
+    for team in report.teams.iter() {
+        let prefix = format!("[{}][{}]", report.year, team.car);
+        for row in team.pairs.iter() {
+            println!("{prefix} {row:?}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use f1scraper::scrape::ScraperConfig;
+
+use crate::commands::output::OutputFormat;
+use crate::prelude::*;
+
+// Settings shared by every command, read from the config file, the environment
+// and the command line, in increasing order of precedence
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub cache_dir: Option<PathBuf>,
+    pub schemas: Option<PathBuf>,
+    pub keep_going: Option<bool>,
+    pub retries: Option<u32>,
+    // requests per second
+    pub rate_limit: Option<f32>,
+    pub progress: Option<bool>,
+    pub year_min: Option<u16>,
+    pub year_max: Option<u16>,
+    pub user_agent: Option<String>,
+    pub proxy: Option<String>,
+    // seconds
+    pub connect_timeout: Option<u64>,
+    pub timeout: Option<u64>,
+    pub root_ca: Option<PathBuf>,
+    pub compression: Option<bool>,
+    pub headers: Option<BTreeMap<String, String>>,
+    // how the commands printing rows print them
+    pub format: Option<OutputFormat>,
+}
+
+// Example:
+//   [default]
+//   cache_dir = "/var/cache/f1scraper"
+//   rate_limit = 2.0
+//   proxy = "http://proxy.corp:3128"
+//   headers = { "X-Requested-By" = "stats-team" }
+//
+//   [profiles.archive]
+//   year_min = 1950
+//   year_max = 1979
+//   keep_going = true
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    default: Settings,
+    #[serde(default)]
+    profiles: BTreeMap<String, Settings>,
+}
+
+impl Settings {
+    // Values set in `other` take precedence, headers are merged by name
+    pub fn merge(self, other: Settings) -> Settings {
+        let headers = match (self.headers, other.headers) {
+            (Some(mut headers), Some(other)) => {
+                headers.extend(other);
+                Some(headers)
+            }
+            (headers, other) => other.or(headers),
+        };
+        Settings {
+            cache_dir: other.cache_dir.or(self.cache_dir),
+            schemas: other.schemas.or(self.schemas),
+            keep_going: other.keep_going.or(self.keep_going),
+            retries: other.retries.or(self.retries),
+            rate_limit: other.rate_limit.or(self.rate_limit),
+            progress: other.progress.or(self.progress),
+            year_min: other.year_min.or(self.year_min),
+            year_max: other.year_max.or(self.year_max),
+            user_agent: other.user_agent.or(self.user_agent),
+            proxy: other.proxy.or(self.proxy),
+            connect_timeout: other.connect_timeout.or(self.connect_timeout),
+            timeout: other.timeout.or(self.timeout),
+            root_ca: other.root_ca.or(self.root_ca),
+            compression: other.compression.or(self.compression),
+            headers,
+            format: other.format.or(self.format),
+        }
+    }
+
+    pub fn scraper_config(&self) -> ScraperConfig {
+        let mut config = ScraperConfig::new();
+        if let Some(user_agent) = &self.user_agent {
+            config = config.with_user_agent(user_agent);
+        }
+        if let Some(proxy) = &self.proxy {
+            config = config.with_proxy(proxy);
+        }
+        if let Some(secs) = self.connect_timeout {
+            config = config.with_connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.timeout {
+            config = config.with_timeout(Duration::from_secs(secs));
+        }
+        if let Some(path) = &self.root_ca {
+            config = config.with_root_certificate(path);
+        }
+        if let Some(compression) = self.compression {
+            config = config.with_compression(compression);
+        }
+        for (name, value) in self.headers.iter().flatten() {
+            config = config.with_header(name, value);
+        }
+        config
+    }
+}
+
+impl Config {
+    // `$XDG_CONFIG_HOME/f1scraper/config.toml`, defaulting to `~/.config`
+    pub fn default_path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(config_home.join("f1scraper").join("config.toml"))
+    }
+
+    // An explicit path must exist, the default one is optional
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        if !required && !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("read config file: {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parse config file: {}", path.display()))
+    }
+
+    // The default settings, overridden by the profile's
+    pub fn settings(&self, profile: Option<&str>) -> Result<Settings> {
+        let settings = self.default.clone();
+        let Some(name) = profile else {
+            return Ok(settings);
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(settings.merge(profile.clone())),
+            None => Err(anyhow::anyhow!(
+                "find profile `{name}` in config file, available profiles: {}",
+                self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use f1scraper::diff::{diff_rows, ResultRow, RowChange};
+
+use crate::commands::ScrapeContext;
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The old dataset, as printed by `race result --format ndjson`
+    old: String,
+
+    /// The new dataset
+    #[arg(required_unless_present = "live")]
+    new: Option<String>,
+
+    /// Scrape the races of the old dataset again instead of reading a new dataset
+    #[arg(long, conflicts_with = "new")]
+    live: bool,
+
+    /// Print the changes as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let mut old = read_dataset(Path::new(&args.old))?;
+    let new = match &args.new {
+        Some(path) => read_dataset(Path::new(path))?,
+        None => {
+            let (new, failed) = query_live(&scrape_ctx, &old)?;
+            // the rows of the pages which failed were not scraped, not removed
+            old.retain(|row| {
+                !failed.iter().any(|(year, idx)| {
+                    *year == row.year && idx.is_none_or(|idx| idx == row.circuit.idx)
+                })
+            });
+            new
+        }
+    };
+
+    let changes = diff_rows(&old, &new);
+    print(&changes, args.json)
+}
+
+fn read_dataset(path: &Path) -> Result<Vec<ResultRow>> {
+    let file = File::open(path).with_context(|| format!("open dataset `{}`", path.display()))?;
+    ResultRow::read_all(BufReader::new(file))
+        .with_context(|| format!("read dataset `{}`", path.display()))
+}
+
+// Year and circuit index of the pages which failed, `None` for a season's summary
+type FailedPages = Vec<(u16, Option<u16>)>;
+
+// Scrape the current results of the races found in the dataset
+fn query_live(
+    scrape_ctx: &ScrapeContext,
+    rows: &[ResultRow],
+) -> Result<(Vec<ResultRow>, FailedPages)> {
+    let races: HashSet<_> = rows.iter().map(|row| (row.year, row.circuit.idx)).collect();
+    let (Some(year_min), Some(year_max)) = (
+        races.iter().map(|(year, _)| *year).min(),
+        races.iter().map(|(year, _)| *year).max(),
+    ) else {
+        anyhow::bail!("dataset is empty, no race to scrape");
+    };
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
+
+    let results = scrape_ctx
+        .scraper
+        .results_between(year_min, year_max)
+        .with_fresh_pages()
+        .with_selection(|year, resolver| {
+            Ok(resolver
+                .items()
+                .filter(|circuit| races.contains(&(year, circuit.idx)))
+                .cloned()
+                .collect())
+        })
+        .with_observer(|event| scrape_ctx.observe(event));
+
+    let mut live = vec![];
+    let mut failed = vec![];
+    for row in results {
+        match row {
+            Ok((year, circuit, entry)) => live.push(ResultRow::new(year, circuit, entry)),
+            Err(err) => {
+                failed.push((err.year, err.circuit.as_ref().map(|c| c.idx)));
+                scrape_ctx.fail(err)?
+            }
+        }
+    }
+    Ok((live, failed))
+}
+
+pub fn print(changes: &[RowChange], json: bool) -> Result<()> {
+    if json {
+        let changes = serde_json::to_string_pretty(changes).context("serialize changes")?;
+        println!("{changes}");
+        return Ok(());
+    }
+
+    for change in changes {
+        let circuit = change.circuit();
+        let prefix = format!(
+            "[{}][{} ({})]",
+            change.year(),
+            circuit.display_name,
+            circuit.name
+        );
+        match change {
+            RowChange::Added { row } => println!("{prefix} added {:?}", row.entry),
+            RowChange::Removed { row } => println!("{prefix} removed {:?}", row.entry),
+            RowChange::Changed { driver, fields, .. } => {
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|f| format!("{} `{}` -> `{}`", f.field, f.old, f.new))
+                    .collect();
+                println!("{prefix} changed {driver}: {}", fields.join(", "));
+            }
+        }
+    }
+    if changes.is_empty() {
+        println!("No differences");
+    }
+    Ok(())
+}
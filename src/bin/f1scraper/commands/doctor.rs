@@ -0,0 +1,279 @@
+use serde::Serialize;
+
+use f1scraper::parse::TableReport;
+use f1scraper::schema::Schemas;
+use f1scraper::scrape::{
+    DriverResultSummaryTarget, DriverResultTarget, FastestLapResultSummaryTarget,
+    QualifyingResultTarget, RaceResultSummaryTarget, RaceResultTarget, ScrapeTarget, Scraper,
+    TeamResultSummaryTarget, TeamResultTarget,
+};
+use f1scraper::types::{
+    DriverResult, DriverSummary, FastestLapSummary, QualifyingResult, RaceResult, RaceSummary,
+    TeamResult, TeamSummary,
+};
+
+use crate::commands::ScrapeContext;
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The season used to sample one page per target kind (defaults to the latest
+    /// season)
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Minimum ratio of table rows that must parse for a page to be healthy
+    #[arg(long, default_value_t = 1.0)]
+    min_parse_rate: f32,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    kind: &'static str,
+    url: Option<String>,
+    healthy: bool,
+    parse_rate: f32,
+    report: Option<TableReport>,
+    error: Option<String>,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let scraper = &scrape_ctx.scraper;
+    let year = match args.year {
+        Some(year) => scrape_ctx.seasons().check(year)?,
+        None => scrape_ctx.seasons().latest,
+    };
+    let mut checks = vec![];
+
+    // summaries also provide the sample entity for the detail pages
+    let html = check(
+        &mut checks,
+        &args,
+        year,
+        scraper,
+        "race-summary",
+        RaceResultSummaryTarget::new(year),
+        RaceSummary::check,
+    );
+    let circuit = sample(html, |html| {
+        RaceSummary::parse_with(html, year, scraper.schemas())?
+            .data
+            .first()
+            .context("no race in summary")?
+            .circuit()
+    });
+    match &circuit {
+        Ok(circuit) => {
+            check(
+                &mut checks,
+                &args,
+                year,
+                scraper,
+                "race-result",
+                RaceResultTarget::new(year, circuit),
+                RaceResult::check,
+            );
+            check(
+                &mut checks,
+                &args,
+                year,
+                scraper,
+                "qualifying-result",
+                QualifyingResultTarget::new(year, circuit),
+                QualifyingResult::check,
+            );
+        }
+        Err(err) => {
+            skip(&mut checks, "race-result", err);
+            skip(&mut checks, "qualifying-result", err);
+        }
+    }
+
+    let html = check(
+        &mut checks,
+        &args,
+        year,
+        scraper,
+        "driver-summary",
+        DriverResultSummaryTarget::new(year),
+        DriverSummary::check,
+    );
+    let driver = sample(html, |html| {
+        DriverSummary::parse_with(html, year, scraper.schemas())?
+            .data
+            .first()
+            .context("no driver in summary")?
+            .driver()
+    });
+    match &driver {
+        Ok(driver) => {
+            check(
+                &mut checks,
+                &args,
+                year,
+                scraper,
+                "driver-result",
+                DriverResultTarget::new(year, driver),
+                DriverResult::check,
+            );
+        }
+        Err(err) => skip(&mut checks, "driver-result", err),
+    }
+
+    let html = check(
+        &mut checks,
+        &args,
+        year,
+        scraper,
+        "team-summary",
+        TeamResultSummaryTarget::new(year),
+        TeamSummary::check,
+    );
+    let team = sample(html, |html| {
+        TeamSummary::parse_with(html, year, scraper.schemas())?
+            .data
+            .first()
+            .context("no team in summary")?
+            .team()
+    });
+    match &team {
+        Ok(team) => {
+            check(
+                &mut checks,
+                &args,
+                year,
+                scraper,
+                "team-result",
+                TeamResultTarget::new(year, team),
+                TeamResult::check,
+            );
+        }
+        Err(err) => skip(&mut checks, "team-result", err),
+    }
+
+    check(
+        &mut checks,
+        &args,
+        year,
+        scraper,
+        "fastest-lap-summary",
+        FastestLapResultSummaryTarget::new(year),
+        FastestLapSummary::check,
+    );
+
+    print(&checks, args.json)?;
+
+    let failed = checks.iter().filter(|c| !c.healthy).count();
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} layout checks failed",
+            failed,
+            checks.len()
+        ));
+    }
+    Ok(())
+}
+
+// Scrape a page and check its layout, returning the page for further sampling
+fn check<T: ScrapeTarget>(
+    checks: &mut Vec<Check>,
+    args: &Args,
+    year: u16,
+    scraper: &Scraper,
+    kind: &'static str,
+    target: Result<T>,
+    check_layout: fn(&str, u16, &Schemas) -> TableReport,
+) -> Option<String> {
+    let result = target.and_then(|target| {
+        let url = target.request().url().to_string();
+        // the cached pages would hide a layout change
+        let html = scraper
+            .scrape_fresh(target)
+            .with_context(|| format!("scrape: {kind} {year}"))?;
+        Ok((url, html))
+    });
+
+    match result {
+        Ok((url, html)) => {
+            let report = check_layout(&html, year, scraper.schemas());
+            checks.push(Check {
+                kind,
+                url: Some(url),
+                healthy: report.is_healthy(args.min_parse_rate),
+                parse_rate: report.parse_rate(),
+                report: Some(report),
+                error: None,
+            });
+            Some(html)
+        }
+        Err(err) => {
+            checks.push(Check {
+                kind,
+                url: None,
+                healthy: false,
+                parse_rate: 0.0,
+                report: None,
+                error: Some(format!("{err:#}")),
+            });
+            None
+        }
+    }
+}
+
+fn sample<T>(html: Option<String>, pick: impl Fn(&str) -> Result<T>) -> Result<T> {
+    let html = html.context("summary page not available")?;
+    pick(&html).context("pick a sample from the summary page")
+}
+
+fn skip(checks: &mut Vec<Check>, kind: &'static str, err: &anyhow::Error) {
+    checks.push(Check {
+        kind,
+        url: None,
+        healthy: false,
+        parse_rate: 0.0,
+        report: None,
+        error: Some(format!("skipped: {err:#}")),
+    });
+}
+
+fn print(checks: &[Check], json: bool) -> Result<()> {
+    if json {
+        let report = serde_json::to_string_pretty(checks).context("serialize report")?;
+        println!("{report}");
+        return Ok(());
+    }
+
+    for check in checks {
+        let status = if check.healthy { "OK" } else { "FAIL" };
+        let rows = check
+            .report
+            .as_ref()
+            .map(|r| format!("{}/{} rows", r.parsed_rows, r.rows))
+            .unwrap_or_else(|| "-".to_string());
+        let url = check.url.as_deref().unwrap_or("-");
+        println!("[{status:<4}] {:<20} {rows:<12} {url}", check.kind);
+
+        if let Some(report) = &check.report {
+            if !report.found {
+                println!("       table not found: `{}`", report.selector);
+            }
+            if !report.missing_headers.is_empty() {
+                println!(
+                    "       missing headers: {:?} (found: {:?})",
+                    report.missing_headers, report.headers
+                );
+            }
+            for err in &report.errors {
+                println!("       {err}");
+            }
+        }
+        if let Some(err) = &check.error {
+            println!("       {err}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use log::info;
+
+use f1scraper::career::Career;
+use f1scraper::registry::DriverRegistry;
+use f1scraper::scrape::Scraper;
+use f1scraper::types::DriverSummaryEntry;
+
+use crate::commands::{race, ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+use super::{result, summary};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The name, id or three-letter code of the driver (e.g. `nino-farina`,
+    /// `NINFAR01`, `farina`)
+    driver_name: String,
+
+    /// Also scrape qualifying results to count pole positions
+    #[arg(long)]
+    qualifying: bool,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+
+    // the driver id is stable across seasons, the name is only resolved until
+    // the driver is found
+    let mut career: Option<Career> = None;
+    let mut unresolved = None;
+    for year in year_min..=year_max {
+        let summary = scrape_ctx.attempt(year, "driver summary", || {
+            summary::query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(summary) = summary? else {
+            continue;
+        };
+        let drivers = summary.drivers()?;
+
+        let id = match &career {
+            Some(career) => career.driver.id.clone(),
+            None => match drivers.resolve(&args.driver_name) {
+                Ok(driver) => driver.id.clone(),
+                Err(err) if err.ambiguous => {
+                    return Err(err).with_context(|| format!("resolve driver for year `{year}`"))
+                }
+                Err(err) => {
+                    unresolved = Some(err);
+                    continue;
+                }
+            },
+        };
+        let found = summary
+            .data
+            .iter()
+            .zip(drivers.items())
+            .find(|(_, driver)| driver.id == id);
+        let Some((entry, driver)) = found else {
+            continue;
+        };
+        info!("[{}] found driver: {:?}", year, driver);
+
+        let career = career.get_or_insert_with(|| Career::new(driver));
+        // the totals miss the seasons which failed, reported at the end
+        let target = format!("driver result {}", driver.display_name);
+        scrape_ctx.attempt(year, &target, || {
+            let registry = DriverRegistry::from_summary(&summary)?;
+            add_season(
+                &scrape_ctx.scraper,
+                career,
+                year,
+                entry,
+                &registry,
+                args.qualifying,
+            )
+        })?;
+    }
+
+    let Some(career) = career else {
+        let context = format!(
+            "find driver between `{}` and `{}` with name: {}",
+            year_min,
+            year_max,
+            args.driver_name.trim()
+        );
+        // the suggestions come from the last season scraped
+        return match unresolved {
+            Some(err) => Err(err).context(context),
+            None => Err(anyhow::anyhow!(context)),
+        };
+    };
+    print(&career)
+}
+
+fn add_season(
+    scraper: &Scraper,
+    career: &mut Career,
+    year: u16,
+    entry: &DriverSummaryEntry,
+    registry: &DriverRegistry,
+    qualifying: bool,
+) -> Result<()> {
+    let results = result::query_and_parse(scraper, year, &career.driver)?;
+
+    let mut qualifying_results = None;
+    if qualifying {
+        let summary = race::summary::query_and_parse(scraper, year)?;
+        let circuits: Result<Vec<_>> = summary.data.iter().map(|gp| gp.circuit()).collect();
+        let circuits = circuits.with_context(|| "obtain circuit infos from summary data")?;
+        qualifying_results = Some(race::qualifying::query_available(scraper, year, &circuits));
+    }
+
+    career.add_season(entry, &results, qualifying_results.as_deref(), registry);
+    Ok(())
+}
+
+fn print(career: &Career) -> Result<()> {
+    let prefix = format!("[{} ({})]", career.driver.display_name, career.driver.id);
+    for season in career.seasons.iter() {
+        println!("{prefix}[{}] {season:?}", season.year);
+    }
+    println!("{prefix}[career] {:?}", career.totals());
+    Ok(())
+}
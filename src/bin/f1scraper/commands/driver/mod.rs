@@ -2,8 +2,10 @@ use crate::prelude::*;
 
 use super::ScrapeContext;
 
+mod career;
+mod registry;
 mod result;
-mod summary;
+pub mod summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -18,11 +20,19 @@ pub enum Commands {
 
     /// Scrape driver standings results
     Result(result::Args),
+
+    /// Aggregate a driver's results across seasons
+    Career(career::Args),
+
+    /// Check that every driver mentioned in a season's results can be identified
+    Registry(registry::Args),
 }
 
 pub fn run(scrape_ctx: ScrapeContext, cmd: Commands) -> Result<()> {
     match cmd {
         Commands::Summary(args) => summary::run(scrape_ctx, args),
         Commands::Result(args) => result::run(scrape_ctx, args),
+        Commands::Career(args) => career::run(scrape_ctx, args),
+        Commands::Registry(args) => registry::run(scrape_ctx, args),
     }
 }
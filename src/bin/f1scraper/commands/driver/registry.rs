@@ -0,0 +1,62 @@
+use f1scraper::registry::{DriverRegistry, RegistryReport};
+
+use crate::commands::{fastestlap, race, ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+use super::summary;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Also resolve the drivers of the qualifying results
+    #[arg(long)]
+    qualifying: bool,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    for year in year_min..=year_max {
+        let scraper = &scrape_ctx.scraper;
+        // every page of the season is needed to audit it
+        let pages = scrape_ctx.attempt(year, "season", || {
+            let drivers = summary::query_and_parse(scraper, year)?;
+            let race_summary = race::summary::query_and_parse(scraper, year)?;
+            let races = race::result::query_season(scraper, year)?;
+            let fastest_laps = fastestlap::summary::query_and_parse(scraper, year)?;
+            Ok((drivers, race_summary, races, fastest_laps))
+        });
+        let Some((drivers, race_summary, races, fastest_laps)) = pages? else {
+            continue;
+        };
+        let registry = DriverRegistry::from_summary(&drivers)?;
+
+        let mut qualifying = vec![];
+        if args.qualifying {
+            let circuits: Vec<_> = races.iter().map(|r| r.circuit.clone()).collect();
+            qualifying = race::qualifying::query_available(scraper, year, &circuits);
+        }
+
+        let report = registry.audit(
+            Some(&race_summary),
+            &races,
+            &qualifying,
+            Some(&fastest_laps),
+        );
+        print(&report)?
+    }
+    Ok(())
+}
+
+fn print(report: &RegistryReport) -> Result<()> {
+    let prefix = format!("[{}]", report.year);
+    println!(
+        "{prefix} resolved {}/{} driver mentions",
+        report.resolved, report.mentions
+    );
+    for row in report.unresolved.iter() {
+        println!("{prefix} {row:?}");
+    }
+    Ok(())
+}
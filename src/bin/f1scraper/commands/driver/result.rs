@@ -1,6 +1,3 @@
-use log::debug;
-use std::collections::HashMap;
-
 use f1scraper::scrape::{DriverResultTarget, Scraper};
 use f1scraper::types::{DriverFragment, DriverResult};
 
@@ -11,74 +8,69 @@ use super::summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
-    /// The name of the Grand Prix
-    driver_name: Option<String>,
+    /// The names, ids or three-letter codes of the drivers, or glob patterns (e.g.
+    /// `verstappen`, `VER`, `*schumacher`)
+    driver_names: Vec<String>,
 
     #[command(flatten)]
     year_flags: YearFlags,
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary and one selection per season, the entities they list are added
+    // on the way
+    scrape_ctx
+        .progress
+        .add_targets(2 * (usize::from(year_max - year_min) + 1));
 
     for year in year_min..=year_max {
         // query summary to obtain the list of available drivers
-        let summary = summary::query_and_parse(&scrape_ctx.scraper, year)?;
-
-        // retrieve drivers
-        // index by name
-        let mut driver_by_name = HashMap::new();
-        let mut driver_by_display_name = HashMap::new();
-        for gp in &summary.data {
-            let driver = gp.driver().with_context(|| {
-                format!(
-                    "obtain driver infos from summary data (driver: `{}`)",
-                    gp.driver
-                )
-            })?;
-            driver_by_name.insert(driver.name.clone().trim().to_lowercase(), driver.clone());
-            driver_by_display_name
-                .insert(driver.display_name.clone().trim().to_lowercase(), driver);
-        }
-        debug!("driver index: {:?}", driver_by_name);
+        let resolver = scrape_ctx.attempt(year, "driver summary", || {
+            summary::query_and_parse(&scrape_ctx.scraper, year)
+                .and_then(|summary| summary.drivers())
+        });
+        let Some(resolver) = resolver? else {
+            continue;
+        };
 
-        // all drivers
-        let mut drivers: Vec<_> = driver_by_name.values().collect();
-
-        // if argument passed, filter by driver name
-        if let Some(driver_name) = &args.driver_name {
-            let driver_name = &driver_name.trim().to_lowercase();
-            let driver_name_indexes = vec![&driver_by_name, &driver_by_display_name];
-            let driver = driver_name_indexes
-                .iter()
-                .filter_map(|index| index.get(driver_name))
-                .next()
-                .ok_or(anyhow::anyhow!(
-                    "find grand prix for year `{}` with name: {}",
-                    year,
-                    driver_name
-                ))?;
-            drivers = vec![driver];
-        }
-        for driver in drivers {
-            let driver_result = query_and_parse(&scrape_ctx.scraper, year, driver)?;
+        // drivers matching the names, all if none passed, a name can be missing
+        // from some seasons
+        let drivers = scrape_ctx.attempt(year, "driver selection", || {
+            resolver
+                .select(&args.driver_names)
+                .with_context(|| format!("resolve drivers for year `{year}`"))
+        });
+        let Some(drivers) = drivers? else {
+            continue;
+        };
+        scrape_ctx.progress.add_targets(drivers.len());
+        for (_, driver) in drivers {
+            let target = format!("driver result {}", driver.display_name);
+            let driver_result = scrape_ctx.attempt(year, &target, || {
+                query_and_parse(&scrape_ctx.scraper, year, driver)
+            });
+            let Some(driver_result) = driver_result? else {
+                continue;
+            };
             print(&driver_result)?
         }
     }
     Ok(())
 }
 
-fn query_and_parse(scraper: &Scraper, year: u16, driver: &DriverFragment) -> Result<DriverResult> {
+pub fn query_and_parse(
+    scraper: &Scraper,
+    year: u16,
+    driver: &DriverFragment,
+) -> Result<DriverResult> {
     // create scrape target
     let target = DriverResultTarget::new(year, driver)
         .with_context(|| format!("create scrape target: driver result {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: driver result {year}"))?;
-    // parse html text as driver result
-    let driver_result = DriverResult::parse(&html, year, &driver.clone())?;
-    Ok(driver_result)
+    // run scrape and parse html text as driver result
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: driver result {year}"))
 }
 
 fn print(driver_result: &DriverResult) -> Result<()> {
@@ -6,15 +6,31 @@ use crate::prelude::*;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
+    /// Aggregate the standings by country instead of printing each driver
+    #[arg(long)]
+    by_country: bool,
+
     #[command(flatten)]
     year_flags: YearFlags,
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary per season
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
     for year in year_min..=year_max {
-        let result = query_and_parse(&scrape_ctx.scraper, year)?;
-        print(&result)?
+        let result = scrape_ctx.attempt(year, "driver summary", || {
+            query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(result) = result? else {
+            continue;
+        };
+        match args.by_country {
+            true => print_by_country(&result)?,
+            false => print(&result)?,
+        }
     }
     Ok(())
 }
@@ -23,13 +39,10 @@ pub fn query_and_parse(scraper: &Scraper, year: u16) -> Result<DriverSummary> {
     // create scrape target
     let target = DriverResultSummaryTarget::new(year)
         .with_context(|| format!("create scrape target: driver result summary {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: driver result summary {year}"))?;
-    // parse html text as driver summary
-    let driver_summary = DriverSummary::parse(&html, year)?;
-    Ok(driver_summary)
+    // run scrape and parse html text as driver summary
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: driver result summary {year}"))
 }
 
 fn print(driver_summary: &DriverSummary) -> Result<()> {
@@ -38,3 +51,53 @@ fn print(driver_summary: &DriverSummary) -> Result<()> {
     }
     Ok(())
 }
+
+#[derive(Debug)]
+struct CountrySummary<'a> {
+    country: &'a str,
+    name: &'a str,
+    drivers: u32,
+    pts: f32,
+    best_pos: Option<u32>,
+}
+
+// Historic nations are counted under their present day country
+fn print_by_country(driver_summary: &DriverSummary) -> Result<()> {
+    let nationalities: Vec<_> = driver_summary
+        .data
+        .iter()
+        .map(|entry| (entry, entry.nationality()))
+        .collect();
+
+    let mut countries: Vec<CountrySummary> = vec![];
+    for (entry, nationality) in nationalities.iter() {
+        let country = nationality.country.as_deref().unwrap_or(&nationality.code);
+        let name = nationality.country_name().unwrap_or(&nationality.code);
+        let idx = match countries.iter().position(|c| c.country == country) {
+            Some(idx) => idx,
+            None => {
+                countries.push(CountrySummary {
+                    country,
+                    name,
+                    drivers: 0,
+                    pts: 0.0,
+                    best_pos: None,
+                });
+                countries.len() - 1
+            }
+        };
+        let summary = &mut countries[idx];
+        summary.drivers += 1;
+        summary.pts += entry.points();
+        summary.best_pos = match (summary.best_pos, entry.position()) {
+            (Some(best), Some(pos)) => Some(best.min(pos)),
+            (best, pos) => best.or(pos),
+        };
+    }
+    countries.sort_by(|a, b| b.pts.total_cmp(&a.pts));
+
+    for row in countries.iter() {
+        println!("[{}][{}] {row:?}", driver_summary.year, row.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::cell::{Cell, RefCell};
+
+use serde::Serialize;
+
+use f1scraper::scrape::{Stage, TargetFailure};
+
+use crate::prelude::*;
+
+// A scrape target which failed during a `--keep-going` run
+#[derive(Debug, Serialize)]
+pub struct Failure {
+    pub year: u16,
+    pub target: String,
+    pub stage: Option<Stage>,
+    pub url: Option<String>,
+    // outermost error first
+    pub errors: Vec<String>,
+}
+
+// Failures of a bulk run, recorded instead of aborting it when keeping going
+#[derive(Debug, Default)]
+pub struct Failures {
+    keep_going: bool,
+    attempts: Cell<usize>,
+    failures: RefCell<Vec<Failure>>,
+}
+
+impl Failures {
+    pub fn new(keep_going: bool) -> Self {
+        Self {
+            keep_going,
+            ..Default::default()
+        }
+    }
+
+    pub fn keep_going(&self) -> bool {
+        self.keep_going
+    }
+
+    // Returns `None` when the target failed and the run keeps going
+    pub fn attempt<T>(&self, year: u16, target: &str, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => {
+                self.succeeded();
+                Ok(Some(value))
+            }
+            Err(err) => self.fail(year, target, err).map(|()| None),
+        }
+    }
+
+    pub fn succeeded(&self) {
+        self.attempts.set(self.attempts.get() + 1);
+    }
+
+    // Record the failure of a target, or return it when not keeping going
+    pub fn fail(&self, year: u16, target: &str, err: anyhow::Error) -> Result<()> {
+        self.attempts.set(self.attempts.get() + 1);
+        if !self.keep_going {
+            return Err(err);
+        }
+
+        let failure = err.downcast_ref::<TargetFailure>();
+        let failure = Failure {
+            year,
+            target: target.to_string(),
+            stage: failure.map(|f| f.stage),
+            url: failure.map(|f| f.url.clone()),
+            errors: err.chain().map(|e| e.to_string()).collect(),
+        };
+        eprintln!(
+            "[{}][{}] failed: {}",
+            failure.year,
+            failure.target,
+            failure.errors.join(": ")
+        );
+        self.failures.borrow_mut().push(failure);
+        Ok(())
+    }
+
+    // Print the error report, failing when any target failed
+    pub fn finish(&self) -> Result<()> {
+        let failures = self.failures.borrow();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        eprintln!("Error report:");
+        for failure in failures.iter() {
+            eprintln!("{}", serde_json::to_string(failure)?);
+        }
+        Err(anyhow::anyhow!(
+            "{} of {} targets failed",
+            failures.len(),
+            self.attempts.get()
+        ))
+    }
+}
@@ -2,7 +2,7 @@ use crate::prelude::*;
 
 use super::ScrapeContext;
 
-mod summary;
+pub mod summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -1,15 +1,38 @@
+use std::cell::OnceCell;
 use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
 
 use clap::Subcommand;
+use log::{debug, warn};
 
-use f1scraper::scrape::Scraper;
+use f1scraper::schema::Schemas;
+use f1scraper::scrape::{PageEvent, ResponseCache, ResultsError, Scraper, SeasonListTarget};
+use f1scraper::season::Seasons;
 
 use crate::prelude::*;
 
+use config::{Config, Settings};
+use failures::Failures;
+use output::OutputFormat;
+use progress::Progress;
+
+mod circuit;
+mod compare;
+mod config;
+mod diff;
+mod doctor;
 mod driver;
+mod failures;
 mod fastestlap;
+mod output;
+mod progress;
 mod race;
+mod serve;
+mod simulate;
 mod team;
+mod watch;
 
 #[derive(Debug, clap::Args)]
 pub struct YearFlags {
@@ -17,32 +40,270 @@ pub struct YearFlags {
     #[arg(short, long)]
     year: Option<u16>,
 
-    /// Minimim year to use when scraping pages
-    #[arg(long, default_value_t = 1950)]
-    year_min: u16,
+    /// Minimim year to use when scraping pages (defaults to the config file, or to
+    /// the first season)
+    #[arg(long)]
+    year_min: Option<u16>,
 
-    /// Maximum year to use when scraping pages
-    #[arg(long, default_value_t = 2023)]
-    year_max: u16,
+    /// Maximum year to use when scraping pages (defaults to the config file, or to
+    /// the latest season)
+    #[arg(long)]
+    year_max: Option<u16>,
 }
 
 impl YearFlags {
-    fn min_max(&self) -> (u16, u16) {
-        match self.year {
-            Some(year) => (year, year),
-            _ => (self.year_min, self.year_max),
+    // Years are checked against the seasons offered by the archive
+    fn min_max(&self, scrape_ctx: &ScrapeContext) -> Result<(u16, u16)> {
+        let seasons = scrape_ctx.seasons();
+        if let Some(year) = self.year {
+            let year = seasons.check(year)?;
+            return Ok((year, year));
+        }
+        let year_min = match self.year_min.or(scrape_ctx.year_min) {
+            Some(year) => seasons.check(year)?,
+            None => seasons.first,
+        };
+        let year_max = match self.year_max.or(scrape_ctx.year_max) {
+            Some(year) => seasons.check(year)?,
+            None => seasons.latest,
+        };
+        if year_min > year_max {
+            return Err(anyhow::anyhow!(
+                "invalid year range: `{year_min}` is after `{year_max}`"
+            ));
+        }
+        Ok((year_min, year_max))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct GlobalFlags {
+    /// Config file (defaults to `$XDG_CONFIG_HOME/f1scraper/config.toml`)
+    #[arg(long, global = true, env = "F1SCRAPER_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Profile of the config file to use on top of its default settings
+    #[arg(long, global = true, env = "F1SCRAPER_PROFILE")]
+    profile: Option<String>,
+
+    /// Cache scraped pages in this directory and reuse them on later runs
+    #[arg(long, global = true, env = "F1SCRAPER_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Page schemas file overriding the built-in page layouts
+    #[arg(long, global = true, env = "F1SCRAPER_SCHEMAS")]
+    schemas: Option<PathBuf>,
+
+    /// Keep scraping the other pages when one fails, and report the failures at
+    /// the end (`--keep-going=false` to turn it off)
+    #[arg(
+        long,
+        global = true,
+        env = "F1SCRAPER_KEEP_GOING",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    keep_going: Option<bool>,
+
+    /// Retry the requests failing with a transient error up to this many times
+    /// [default: 0]
+    #[arg(long, global = true, env = "F1SCRAPER_RETRIES")]
+    retries: Option<u32>,
+
+    /// Maximum number of requests per second
+    #[arg(long, global = true, env = "F1SCRAPER_RATE_LIMIT")]
+    rate_limit: Option<f32>,
+
+    /// Hide the progress bar and the run summary (`--no-progress=false` to show
+    /// them)
+    #[arg(
+        long,
+        global = true,
+        env = "F1SCRAPER_NO_PROGRESS",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    no_progress: Option<bool>,
+
+    /// User agent sent with the requests [default: f1scraper/<version>]
+    #[arg(long, global = true, env = "F1SCRAPER_USER_AGENT")]
+    user_agent: Option<String>,
+
+    /// Proxy used for every request (e.g. `http://proxy.corp:3128`)
+    #[arg(long, global = true, env = "F1SCRAPER_PROXY")]
+    proxy: Option<String>,
+
+    /// Timeout in seconds to connect to the website
+    #[arg(
+        long,
+        value_name = "SECS",
+        global = true,
+        env = "F1SCRAPER_CONNECT_TIMEOUT"
+    )]
+    connect_timeout: Option<u64>,
+
+    /// Timeout in seconds of a whole request [default: 30]
+    #[arg(long, value_name = "SECS", global = true, env = "F1SCRAPER_TIMEOUT")]
+    timeout: Option<u64>,
+
+    /// Additional root certificate to trust, PEM or DER encoded
+    #[arg(long, global = true, env = "F1SCRAPER_ROOT_CA")]
+    root_ca: Option<PathBuf>,
+
+    /// Don't ask for compressed responses (`--no-compression=false` to ask for
+    /// them)
+    #[arg(
+        long,
+        global = true,
+        env = "F1SCRAPER_NO_COMPRESSION",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    no_compression: Option<bool>,
+
+    /// Header sent with every request (e.g. `X-Requested-By: stats-team`)
+    #[arg(long = "header", value_name = "NAME: VALUE", global = true, value_parser = parse_header)]
+    headers: Vec<(String, String)>,
+}
+
+impl GlobalFlags {
+    // Flags left unset don't override the config file
+    fn settings(&self) -> Settings {
+        Settings {
+            cache_dir: self.cache_dir.clone(),
+            schemas: self.schemas.clone(),
+            keep_going: self.keep_going,
+            retries: self.retries,
+            rate_limit: self.rate_limit,
+            progress: self.no_progress.map(|no_progress| !no_progress),
+            year_min: None,
+            year_max: None,
+            user_agent: self.user_agent.clone(),
+            proxy: self.proxy.clone(),
+            connect_timeout: self.connect_timeout,
+            timeout: self.timeout,
+            root_ca: self.root_ca.clone(),
+            compression: self.no_compression.map(|no_compression| !no_compression),
+            headers: (!self.headers.is_empty()).then(|| self.headers.iter().cloned().collect()),
+            // set by the commands printing rows
+            format: None,
         }
     }
 }
 
-#[derive(Default)]
+fn parse_header(s: &str) -> Result<(String, String), String> {
+    match s.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected `NAME: VALUE`, got `{s}`")),
+    }
+}
+
+#[derive(Clone)]
 pub struct ScrapeContext {
-    scraper: Scraper,
+    scraper: Rc<Scraper>,
+    failures: Rc<Failures>,
+    progress: Rc<Progress>,
+    // default year range of the config file
+    year_min: Option<u16>,
+    year_max: Option<u16>,
+    // default output format of the config file
+    format: Option<OutputFormat>,
+    // looked up once per run
+    seasons: Rc<OnceCell<Seasons>>,
 }
 
 impl ScrapeContext {
-    fn new(scraper: Scraper) -> Self {
-        Self { scraper }
+    fn new(scraper: Scraper, settings: &Settings) -> Self {
+        Self {
+            scraper: Rc::new(scraper),
+            failures: Rc::new(Failures::new(settings.keep_going.unwrap_or(false))),
+            progress: Rc::new(Progress::new(settings.progress.unwrap_or(true))),
+            year_min: settings.year_min,
+            year_max: settings.year_max,
+            format: settings.format,
+            seasons: Rc::new(OnceCell::new()),
+        }
+    }
+
+    // Scrape one target of a bulk run, returning `None` when it failed and the
+    // run keeps going
+    fn attempt<T>(
+        &self,
+        year: u16,
+        target: &str,
+        scrape: impl FnOnce() -> Result<T>,
+    ) -> Result<Option<T>> {
+        self.progress.start(&format!("[{year}] {target}"));
+        let result = scrape();
+        self.progress.finish();
+        self.failures.attempt(year, target, result)
+    }
+
+    // Track the pages scraped by a library iterator like `attempt` does
+    fn observe(&self, event: PageEvent) {
+        match event {
+            PageEvent::Started { year, target } => {
+                self.progress.start(&format!("[{year}] {target}"))
+            }
+            PageEvent::Finished { ok, .. } => {
+                self.progress.finish();
+                if ok {
+                    self.failures.succeeded();
+                }
+            }
+            PageEvent::Listed { count, .. } => self.progress.add_targets(count),
+        }
+    }
+
+    // Record a page a library iterator failed to scrape, failing when not
+    // keeping going
+    fn fail(&self, err: ResultsError) -> Result<()> {
+        self.failures.fail(err.year, &err.target, err.error)
+    }
+
+    // Print the run summary and the error report, failing when any target failed
+    fn finish(&self) -> Result<()> {
+        let stats = self.scraper.stats();
+        if self.progress.is_enabled() && stats.pages() > 0 {
+            eprintln!("Run summary: {stats}");
+        }
+        self.failures.finish()
+    }
+
+    // Seasons listed by the archive, looked up once per run. A listing cached
+    // less than a day ago is reused, an older one only when the archive can't be
+    // reached, and the clock is the last resort
+    fn seasons(&self) -> Seasons {
+        const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+        *self.seasons.get_or_init(|| {
+            let query = |max_age: Option<Duration>| -> Result<Seasons> {
+                let target = SeasonListTarget::new()?;
+                match max_age {
+                    Some(max_age) => self.scraper.fetch_within(target, max_age),
+                    None => self.scraper.fetch(target),
+                }
+                .context("scrape: season list")
+            };
+            query(Some(MAX_AGE))
+                .or_else(|err| {
+                    warn!("{err:#}, using the cached season list");
+                    query(None)
+                })
+                .unwrap_or_else(|err| {
+                    let seasons = Seasons::from_clock();
+                    warn!("{err:#}, assuming the latest season is {}", seasons.latest);
+                    seasons
+                })
+        })
     }
 }
 
@@ -65,6 +326,27 @@ pub enum Commands {
 
     /// Scrape fastest laps
     FastestLap(fastestlap::Args),
+
+    /// Scrape circuit histories
+    Circuit(circuit::Args),
+
+    /// Compare drivers
+    Compare(compare::Args),
+
+    /// Re-rank a season under another points system
+    Simulate(simulate::Args),
+
+    /// Serve scraped data as JSON over HTTP
+    Serve(serve::Args),
+
+    /// Check that the website layout still matches the parsers
+    Doctor(doctor::Args),
+
+    /// Compare two race result datasets, or a dataset with the live website
+    Diff(diff::Args),
+
+    /// Poll a season's results and report the new or changed ones
+    Watch(watch::Args),
 }
 
 impl fmt::Display for Commands {
@@ -74,17 +356,57 @@ impl fmt::Display for Commands {
             Commands::Driver(_) => write!(f, "driver"),
             Commands::Team(_) => write!(f, "team"),
             Commands::FastestLap(_) => write!(f, "fastest-lap"),
+            Commands::Circuit(_) => write!(f, "circuit"),
+            Commands::Compare(_) => write!(f, "compare"),
+            Commands::Simulate(_) => write!(f, "simulate"),
+            Commands::Serve(_) => write!(f, "serve"),
+            Commands::Doctor(_) => write!(f, "doctor"),
+            Commands::Diff(_) => write!(f, "diff"),
+            Commands::Watch(_) => write!(f, "watch"),
         }
     }
 }
 
-pub fn process(cmd: Commands) -> Result<()> {
-    let client = reqwest::blocking::Client::new();
-    let ctx = ScrapeContext::new(Scraper::new(client));
-    match cmd {
-        Commands::Race(args) => race::run(ctx, args.command),
-        Commands::Driver(args) => driver::run(ctx, args.command),
-        Commands::Team(args) => team::process(ctx, args.command),
-        Commands::FastestLap(args) => fastestlap::process(ctx, args.command),
+pub fn process(cmd: Commands, global_flags: GlobalFlags) -> Result<()> {
+    // command line flags and environment variables take precedence over the
+    // config file
+    let config = Config::load(global_flags.config.as_deref())?;
+    let settings = config
+        .settings(global_flags.profile.as_deref())?
+        .merge(global_flags.settings());
+    debug!("Settings: {settings:?}");
+
+    let mut scraper = settings.scraper_config().build()?;
+    match (settings.cache_dir.clone(), &cmd) {
+        (Some(cache_dir), _) => scraper = scraper.with_cache(ResponseCache::on_disk(cache_dir)),
+        // the server answers the same pages over and over
+        (None, Commands::Serve(_)) => scraper = scraper.with_cache(ResponseCache::in_memory()),
+        _ => {}
+    }
+    if let Some(path) = &settings.schemas {
+        let schemas = Schemas::load(path)?;
+        scraper = scraper.with_schemas(Schemas::builtin().extend(schemas));
+    }
+    if let Some(rate_limit) = settings.rate_limit {
+        scraper = scraper.with_rate_limit(rate_limit);
     }
+    scraper = scraper.with_retries(settings.retries.unwrap_or(0));
+    let ctx = ScrapeContext::new(scraper, &settings);
+    let result = match cmd {
+        Commands::Race(args) => race::run(ctx.clone(), args.command),
+        Commands::Driver(args) => driver::run(ctx.clone(), args.command),
+        Commands::Team(args) => team::process(ctx.clone(), args.command),
+        Commands::FastestLap(args) => fastestlap::process(ctx.clone(), args.command),
+        Commands::Circuit(args) => circuit::run(ctx.clone(), args.command),
+        Commands::Compare(args) => compare::run(ctx.clone(), args.command),
+        Commands::Simulate(args) => simulate::run(ctx.clone(), args),
+        Commands::Serve(args) => serve::run(ctx.clone(), args),
+        Commands::Doctor(args) => doctor::run(ctx.clone(), args),
+        Commands::Diff(args) => diff::run(ctx.clone(), args),
+        Commands::Watch(args) => watch::run(ctx.clone(), args),
+    };
+    // report the failures even when the command was aborted
+    let report = ctx.finish();
+    result?;
+    report
 }
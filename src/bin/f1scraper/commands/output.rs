@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+// How the rows of a bulk run are printed to stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    // one `[year][...] Row { .. }` line per row
+    #[default]
+    Debug,
+    // one JSON object per line
+    Ndjson,
+    // comma-separated values, with a header line
+    Csv,
+}
+
+// Join the fields as a CSV record, quoting the ones which need it
+pub fn csv_record<'f>(fields: impl IntoIterator<Item = &'f str>) -> String {
+    fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+            } else {
+                Cow::Borrowed(field)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,71 @@
+use std::cell::Cell;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// Progress of the scrape targets of a bulk run, drawn as a bar on stderr when
+// it is a terminal and logged periodically otherwise
+#[derive(Debug)]
+pub struct Progress {
+    enabled: bool,
+    tty: bool,
+    total: Cell<usize>,
+    done: Cell<usize>,
+    logged_at: Cell<Option<Instant>>,
+}
+
+impl Progress {
+    const BAR_WIDTH: usize = 30;
+    const LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            tty: io::stderr().is_terminal(),
+            total: Cell::new(0),
+            done: Cell::new(0),
+            logged_at: Cell::new(None),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // Targets become known as the summaries listing them are scraped
+    pub fn add_targets(&self, n: usize) {
+        self.total.set(self.total.get() + n);
+    }
+
+    // Show the target being scraped, until `finish` is called
+    pub fn start(&self, target: &str) {
+        if !self.enabled {
+            return;
+        }
+        let (done, total) = (self.done.get(), self.total.get().max(self.done.get() + 1));
+        if self.tty {
+            let filled = Self::BAR_WIDTH * done / total;
+            eprint!(
+                "\r\x1b[2K[{}{}] {done}/{total} {target}",
+                "#".repeat(filled),
+                "-".repeat(Self::BAR_WIDTH - filled)
+            );
+            let _ = io::stderr().flush();
+        } else if self
+            .logged_at
+            .get()
+            .is_none_or(|at| at.elapsed() >= Self::LOG_INTERVAL)
+        {
+            eprintln!("Progress: {done}/{total} targets scraped, scraping {target}");
+            self.logged_at.set(Some(Instant::now()));
+        }
+    }
+
+    // Clear the bar so the results are printed on a blank line
+    pub fn finish(&self) {
+        self.done.set(self.done.get() + 1);
+        if self.enabled && self.tty {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        }
+    }
+}
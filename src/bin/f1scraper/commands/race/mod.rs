@@ -2,8 +2,9 @@ use crate::prelude::*;
 
 use super::ScrapeContext;
 
-mod result;
-mod summary;
+pub mod qualifying;
+pub mod result;
+pub mod summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -18,11 +19,15 @@ pub enum Commands {
 
     /// Scrape race results
     Result(result::Args),
+
+    /// Scrape qualifying results
+    Qualifying(qualifying::Args),
 }
 
 pub fn run(scrape_ctx: ScrapeContext, cmd: Commands) -> Result<()> {
     match cmd {
         Commands::Summary(args) => summary::run(scrape_ctx, args),
         Commands::Result(args) => result::run(scrape_ctx, args),
+        Commands::Qualifying(args) => qualifying::run(scrape_ctx, args),
     }
 }
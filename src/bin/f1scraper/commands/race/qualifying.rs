@@ -0,0 +1,97 @@
+use log::warn;
+
+use f1scraper::scrape::{QualifyingResultTarget, Scraper};
+use f1scraper::types::{Circuit, QualifyingResult};
+
+use crate::commands::{ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+use super::summary;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary per season, the entities they list are added on the way
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
+    for year in year_min..=year_max {
+        let summary = scrape_ctx.attempt(year, "race summary", || {
+            summary::query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(summary) = summary? else {
+            continue;
+        };
+        scrape_ctx.progress.add_targets(summary.data.len());
+        for gp in &summary.data {
+            let target = format!("qualifying result {}", gp.grand_prix);
+
+            let qualifying = scrape_ctx.attempt(year, &target, || {
+                gp.circuit()
+                    .with_context(|| {
+                        format!(
+                            "obtain circuit infos from summary data (circuit: `{}`)",
+                            gp.grand_prix
+                        )
+                    })
+                    .and_then(|circuit| query_and_parse(&scrape_ctx.scraper, year, &circuit))
+            });
+            let Some(qualifying) = qualifying? else {
+                continue;
+            };
+            print(&qualifying)?
+        }
+    }
+    Ok(())
+}
+
+pub fn query_and_parse(
+    scraper: &Scraper,
+    year: u16,
+    circuit: &Circuit,
+) -> Result<QualifyingResult> {
+    // create scrape target
+    let target = QualifyingResultTarget::new(year, circuit)
+        .with_context(|| format!("create scrape target: qualifying result {year}"))?;
+    // run scrape and parse html text as qualifying result
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: qualifying result {year}"))
+}
+
+// Query the qualifying results of the given races, skipping the ones not published
+pub fn query_available(
+    scraper: &Scraper,
+    year: u16,
+    circuits: &[Circuit],
+) -> Vec<QualifyingResult> {
+    let mut qualifying_results = Vec::with_capacity(circuits.len());
+    for circuit in circuits {
+        match query_and_parse(scraper, year, circuit) {
+            Ok(result) => qualifying_results.push(result),
+            Err(err) => warn!(
+                "[{}][{}] qualifying not available: {:#}",
+                year, circuit.display_name, err
+            ),
+        }
+    }
+    qualifying_results
+}
+
+fn print(qualifying_result: &QualifyingResult) -> Result<()> {
+    let prefix = format!(
+        "[{}][{} ({})]",
+        qualifying_result.year,
+        qualifying_result.circuit.display_name,
+        qualifying_result.circuit.name
+    );
+    for row in qualifying_result.data.iter() {
+        println!("{prefix} {row:?}");
+    }
+    Ok(())
+}
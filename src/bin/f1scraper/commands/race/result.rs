@@ -1,9 +1,11 @@
-use log::debug;
-use std::collections::HashMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
 
+use f1scraper::diff::ResultRow;
 use f1scraper::scrape::{RaceResultTarget, Scraper};
 use f1scraper::types::{Circuit, RaceResult};
 
+use crate::commands::output::{csv_record, OutputFormat};
 use crate::commands::{ScrapeContext, YearFlags};
 use crate::prelude::*;
 
@@ -11,94 +13,180 @@ use super::summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
-    /// The name of the Grand Prix
-    circuit_name: Option<String>,
+    /// The names of the Grand Prix, or glob patterns (e.g. `saudi*`)
+    circuit_names: Vec<String>,
+
+    /// Only scrape the N-th race of each season (can be repeated)
+    #[arg(long = "round", value_name = "N")]
+    rounds: Vec<usize>,
+
+    /// Only scrape a range of rounds of each season (e.g. `3-7`)
+    #[arg(long, value_name = "FIRST-LAST", value_parser = parse_round_range)]
+    round_range: Option<RangeInclusive<usize>>,
+
+    /// How to print the rows (defaults to the config file, or to `debug`)
+    #[arg(long, value_enum, env = "F1SCRAPER_FORMAT")]
+    format: Option<OutputFormat>,
 
     #[command(flatten)]
     year_flags: YearFlags,
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
-
-    for year in year_min..=year_max {
-        // query summary to obtain the list of available circuits
-        let summary = summary::query_and_parse(&scrape_ctx.scraper, year)?;
-
-        // retrieve circuits
-        // index by name
-        let mut circuit_by_name = HashMap::new();
-        let mut circuit_by_display_name = HashMap::new();
-        for gp in &summary.data {
-            let circuit = gp.circuit().with_context(|| {
-                format!(
-                    "obtain circuit infos from summary data (circuit: `{}`)",
-                    gp.grand_prix
-                )
-            })?;
-            circuit_by_name.insert(circuit.name.clone().trim().to_lowercase(), circuit.clone());
-            circuit_by_display_name
-                .insert(circuit.display_name.clone().trim().to_lowercase(), circuit);
-        }
-        debug!("circuit index: {:?}", circuit_by_name);
-
-        // all circuits
-        let mut circuits: Vec<_> = circuit_by_name.values().collect();
-
-        // if argument passed, filter by circuit name
-        if let Some(circuit_name) = &args.circuit_name {
-            let circuit_name = &circuit_name.trim().to_lowercase();
-            let circuit_name_indexes = vec![&circuit_by_name, &circuit_by_display_name];
-            let circuit = circuit_name_indexes
-                .iter()
-                .filter_map(|index| index.get(circuit_name))
-                .next()
-                .ok_or(anyhow::anyhow!(
-                    "find grand prix for year `{}` with name: {}",
-                    year,
-                    circuit_name
-                ))?;
-            circuits = vec![circuit];
-        }
-        for circuit in circuits {
-            let race_result = query_and_parse(&scrape_ctx.scraper, year, circuit)?;
-            print(&race_result)?
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    let format = args.format.or(scrape_ctx.format).unwrap_or_default();
+    // one summary per season, the entities they list are added on the way
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
+
+    // rows are printed as the pages are scraped, one page at a time
+    let results = scrape_ctx
+        .scraper
+        .results_between(year_min, year_max)
+        .with_selection(|year, resolver| {
+            // circuits matching the names (all if none passed), in calendar order
+            let circuits = resolver
+                .select(&args.circuit_names)
+                .with_context(|| format!("resolve circuits for year `{year}`"))?;
+            // filter by round, rounds being numbered from 1
+            Ok(circuits
+                .into_iter()
+                .filter(|(idx, _)| args.has_round(idx + 1))
+                .map(|(_, circuit)| circuit.clone())
+                .collect())
+        })
+        .with_observer(|event| scrape_ctx.observe(event));
+
+    let mut out = io::stdout().lock();
+    if format == OutputFormat::Csv {
+        writeln!(out, "{}", csv_record(CSV_HEADER))?;
+    }
+    for row in results {
+        match row {
+            Ok((year, circuit, entry)) => {
+                print(&mut out, format, &ResultRow::new(year, circuit, entry))?
+            }
+            Err(err) => scrape_ctx.fail(err)?,
         }
     }
     Ok(())
 }
 
-fn query_and_parse(scraper: &Scraper, year: u16, circuit: &Circuit) -> Result<RaceResult> {
+impl Args {
+    fn has_round(&self, round: usize) -> bool {
+        if self.rounds.is_empty() && self.round_range.is_none() {
+            return true;
+        }
+        self.rounds.contains(&round)
+            || self
+                .round_range
+                .as_ref()
+                .is_some_and(|r| r.contains(&round))
+    }
+}
+
+fn parse_round_range(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let (first, last) = s
+        .split_once('-')
+        .ok_or_else(|| format!("expected `FIRST-LAST`, got `{s}`"))?;
+    let parse = |round: &str| {
+        round
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("invalid round `{round}`: {e}"))
+    };
+    let (first, last) = (parse(first)?, parse(last)?);
+    if first == 0 || first > last {
+        return Err(format!("invalid round range `{s}`"));
+    }
+    Ok(first..=last)
+}
+
+pub fn query_and_parse(scraper: &Scraper, year: u16, circuit: &Circuit) -> Result<RaceResult> {
     // create scrape target
     let target = RaceResultTarget::new(year, circuit)
         .with_context(|| format!("create scrape target: race result {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: race result {year}"))?;
-
-    // parse html text as race result
-    let race_result = RaceResult::parse(&html, year, &circuit.clone())?;
-    Ok(race_result)
+    // run scrape and parse html text as race result
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: race result {year}"))
 }
 
-fn print(race_result: &RaceResult) -> Result<()> {
-    let default_value = "-".to_string();
-    let mut circuit_name = &race_result.circuit.name;
-    if circuit_name.is_empty() {
-        circuit_name = &default_value;
-    }
-    let mut circuit_display_name = &race_result.circuit.display_name;
-    if circuit_display_name.is_empty() {
-        circuit_display_name = &default_value;
+// Query the results of every race of a season, in calendar order
+pub fn query_season(scraper: &Scraper, year: u16) -> Result<Vec<RaceResult>> {
+    let summary = summary::query_and_parse(scraper, year)?;
+    let mut race_results = Vec::with_capacity(summary.data.len());
+    for gp in &summary.data {
+        let circuit = gp.circuit().with_context(|| {
+            format!(
+                "obtain circuit infos from summary data (circuit: `{}`)",
+                gp.grand_prix
+            )
+        })?;
+        race_results.push(query_and_parse(scraper, year, &circuit)?);
     }
+    Ok(race_results)
+}
+
+const CSV_HEADER: [&str; 13] = [
+    "year",
+    "circuit",
+    "grand_prix",
+    "pos",
+    "no",
+    "driver",
+    "first_name",
+    "last_name",
+    "code",
+    "car",
+    "laps",
+    "time_retired",
+    "pts",
+];
 
-    let prefix = format!(
-        "[{}][{} ({})]",
-        race_result.year, circuit_display_name, circuit_name
-    );
-    for row in race_result.data.iter() {
-        println!("{prefix} {row:?}");
+fn print(out: &mut impl Write, format: OutputFormat, row: &ResultRow) -> Result<()> {
+    let ResultRow {
+        year,
+        circuit,
+        entry,
+    } = row;
+    match format {
+        OutputFormat::Debug => {
+            let or_default = |s: &str| {
+                if s.is_empty() {
+                    "-".to_string()
+                } else {
+                    s.to_string()
+                }
+            };
+            let (name, display_name) =
+                (or_default(&circuit.name), or_default(&circuit.display_name));
+            writeln!(out, "[{year}][{display_name} ({name})] {entry:?}")?;
+        }
+        OutputFormat::Ndjson => {
+            writeln!(out, "{}", serde_json::to_string(row)?)?;
+        }
+        OutputFormat::Csv => {
+            let year = year.to_string();
+            let name = &entry.driver_name;
+            let record = csv_record([
+                year.as_str(),
+                &circuit.name,
+                &circuit.display_name,
+                &entry.pos,
+                &entry.no,
+                &entry.driver,
+                &name.first,
+                &name.last,
+                name.code.as_deref().unwrap_or_default(),
+                &entry.car,
+                &entry.laps,
+                &entry.time_retired,
+                &entry.pts,
+            ]);
+            writeln!(out, "{record}")?;
+        }
     }
     Ok(())
 }
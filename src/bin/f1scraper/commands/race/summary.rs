@@ -11,9 +11,18 @@ pub struct Args {
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary per season
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
     for year in year_min..=year_max {
-        let result = query_and_parse(&scrape_ctx.scraper, year)?;
+        let result = scrape_ctx.attempt(year, "race summary", || {
+            query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(result) = result? else {
+            continue;
+        };
         print(&result)?
     }
     Ok(())
@@ -23,13 +32,10 @@ pub fn query_and_parse(scraper: &Scraper, year: u16) -> Result<RaceSummary> {
     // create scrape target
     let target = RaceResultSummaryTarget::new(year)
         .with_context(|| format!("create scrape target: race result summary {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: race result summary {year}"))?;
-    // parse html text as race summary
-    let summaries = RaceSummary::parse(&html, year)?;
-    Ok(summaries)
+    // run scrape and parse html text as race summary
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: race result summary {year}"))
 }
 
 fn print(summaries: &RaceSummary) -> Result<()> {
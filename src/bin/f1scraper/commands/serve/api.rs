@@ -0,0 +1,184 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use f1scraper::types::{
+    DriverSummaryEntry, FastestLapSummaryEntry, QualifyingResultEntry, RaceResultEntry,
+    RaceSummaryEntry, TeamSummaryEntry,
+};
+
+use super::store::Store;
+use super::{ApiError, Request};
+
+const DEFAULT_LIMIT: usize = 30;
+const MAX_LIMIT: usize = 1000;
+
+// Routes:
+//   /seasons
+//   /seasons/{year}/races
+//   /seasons/{year}/races/{idx}/results
+//   /seasons/{year}/races/{idx}/qualifying
+//   /seasons/{year}/drivers
+//   /seasons/{year}/teams
+//   /seasons/{year}/fastest-laps
+pub fn handle(store: &Store, req: &Request) -> Result<Value, ApiError> {
+    let page = PageQuery::parse(req)?;
+    let segments: Vec<_> = req.segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        ["seasons"] => page.apply(&store.seasons(), |_| true),
+        ["seasons", year, rest @ ..] => {
+            let year = parse_segment::<u16>(year, "year")?;
+            match rest {
+                ["races"] => page.apply(&store.races(year)?.data, |e| page.matches(e)),
+                ["races", idx, "results"] => {
+                    let idx = parse_segment::<u16>(idx, "race index")?;
+                    page.apply(&store.race_result(year, idx)?.data, |e| page.matches(e))
+                }
+                ["races", idx, "qualifying"] => {
+                    let idx = parse_segment::<u16>(idx, "race index")?;
+                    page.apply(&store.qualifying(year, idx)?.data, |e| page.matches(e))
+                }
+                ["drivers"] => page.apply(&store.drivers(year)?.data, |e| page.matches(e)),
+                ["teams"] => page.apply(&store.teams(year)?.data, |e| page.matches(e)),
+                ["fastest-laps"] => {
+                    page.apply(&store.fastest_laps(year)?.data, |e| page.matches(e))
+                }
+                _ => Err(ApiError::not_found(format!("no route: {}", req.path))),
+            }
+        }
+        _ => Err(ApiError::not_found(format!("no route: {}", req.path))),
+    }
+}
+
+fn parse_segment<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("invalid {name}: `{value}`")))
+}
+
+#[derive(Serialize)]
+struct Page<'a, T: Serialize> {
+    total: usize,
+    offset: usize,
+    limit: usize,
+    data: Vec<&'a T>,
+}
+
+struct PageQuery {
+    offset: usize,
+    limit: usize,
+    driver: Option<String>,
+    team: Option<String>,
+}
+
+impl PageQuery {
+    fn parse(req: &Request) -> Result<Self, ApiError> {
+        let mut query = Self {
+            offset: 0,
+            limit: DEFAULT_LIMIT,
+            driver: None,
+            team: None,
+        };
+        for (key, value) in &req.query {
+            match key.as_str() {
+                "offset" => query.offset = parse_segment(value, "offset")?,
+                "limit" => query.limit = parse_segment::<usize>(value, "limit")?.min(MAX_LIMIT),
+                "driver" => query.driver = Some(value.trim().to_lowercase()),
+                "team" => query.team = Some(value.trim().to_lowercase()),
+                _ => {
+                    return Err(ApiError::bad_request(format!(
+                        "unknown query parameter: `{key}`"
+                    )))
+                }
+            }
+        }
+        Ok(query)
+    }
+
+    fn apply<T: Serialize>(
+        &self,
+        rows: &[T],
+        filter: impl Fn(&T) -> bool,
+    ) -> Result<Value, ApiError> {
+        let rows: Vec<_> = rows.iter().filter(|row| filter(row)).collect();
+        let page = Page {
+            total: rows.len(),
+            offset: self.offset,
+            limit: self.limit,
+            data: rows
+                .into_iter()
+                .skip(self.offset)
+                .take(self.limit)
+                .collect(),
+        };
+        serde_json::to_value(page).map_err(|err| ApiError::internal(err.into()))
+    }
+
+    fn matches(&self, entry: &impl Filterable) -> bool {
+        let contains = |filter: &Option<String>, value: Option<&str>| match (filter, value) {
+            (None, _) => true,
+            (Some(filter), Some(value)) => value.to_lowercase().contains(filter),
+            (Some(_), None) => false,
+        };
+        contains(&self.driver, entry.driver()) && contains(&self.team, entry.team())
+    }
+}
+
+// Fields used by the `driver` and `team` query filters
+trait Filterable {
+    fn driver(&self) -> Option<&str>;
+    fn team(&self) -> Option<&str>;
+}
+
+impl Filterable for RaceSummaryEntry {
+    fn driver(&self) -> Option<&str> {
+        Some(&self.winner)
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.car)
+    }
+}
+
+impl Filterable for RaceResultEntry {
+    fn driver(&self) -> Option<&str> {
+        Some(&self.driver)
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.car)
+    }
+}
+
+impl Filterable for QualifyingResultEntry {
+    fn driver(&self) -> Option<&str> {
+        Some(&self.driver)
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.car)
+    }
+}
+
+impl Filterable for DriverSummaryEntry {
+    fn driver(&self) -> Option<&str> {
+        Some(&self.driver)
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.car)
+    }
+}
+
+impl Filterable for TeamSummaryEntry {
+    fn driver(&self) -> Option<&str> {
+        None
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.team)
+    }
+}
+
+impl Filterable for FastestLapSummaryEntry {
+    fn driver(&self) -> Option<&str> {
+        Some(&self.driver)
+    }
+    fn team(&self) -> Option<&str> {
+        Some(&self.car)
+    }
+}
@@ -0,0 +1,467 @@
+use chrono::NaiveDate;
+use serde_json::{json, Map, Value};
+
+use f1scraper::resolve::slug;
+use f1scraper::types::{
+    Circuit, DriverName, RaceResultEntry, RaceSummary, RaceSummaryEntry, Venue,
+};
+
+use super::store::Store;
+use super::{xml, ApiError, Body, Request};
+
+pub const PREFIX: [&str; 2] = ["api", "f1"];
+
+const XMLNS: &str = "http://ergast.com/mrd/1.5";
+const DEFAULT_LIMIT: usize = 30;
+const MAX_LIMIT: usize = 1000;
+
+// Subset of the Ergast API, served under `/api/f1`:
+//   /{season}.json, /{season}/races.json, /{season}/{round}.json
+//   /{season}/results.json, /{season}/{round}/results.json
+//   /{season}/driverStandings.json, /{season}/constructorStandings.json
+//   /{season}/fastest/1/results.json
+//
+// Responses are XML unless the path ends with `.json`, like Ergast's. `current` and
+// `last` are accepted for the season and the round. Identifiers are derived from
+// the archive's names and slugs so they don't always match Ergast's.
+pub fn handle(store: &Store, req: &Request) -> Result<Body, ApiError> {
+    let no_route = || ApiError::not_found(format!("no route: {}", req.path));
+
+    let mut segments: Vec<_> = req
+        .segments
+        .iter()
+        .skip(PREFIX.len())
+        .map(String::as_str)
+        .collect();
+    let last = segments.pop().ok_or_else(no_route)?;
+    let (last, json) = match last.rsplit_once('.') {
+        Some((last, "json")) => (last, true),
+        Some((last, "xml")) => (last, false),
+        Some((_, format)) => {
+            return Err(ApiError::bad_request(format!(
+                "unsupported format: `{format}`, only json and xml are supported"
+            )))
+        }
+        None => (last, false),
+    };
+    segments.push(last);
+
+    let page = Page::parse(req)?;
+    let url = format!("http://localhost{}", req.path);
+    let (season, rest) = segments.split_first().ok_or_else(no_route)?;
+    let season = parse_season(store, season)?;
+
+    let mr_data = match rest {
+        [] | ["races"] => races(store, season, None, &page, &url),
+        ["results"] => results(store, season, None, &page, &url),
+        ["driverStandings"] => driver_standings(store, season, &page, &url),
+        ["constructorStandings"] => constructor_standings(store, season, &page, &url),
+        ["fastest", "1", "results"] => fastest_laps(store, season, &page, &url),
+        [round] => races(store, season, Some(round), &page, &url),
+        [round, "results"] => results(store, season, Some(round), &page, &url),
+        _ => Err(no_route()),
+    }?;
+    match json {
+        true => Ok(Body::Json(mr_data)),
+        false => Ok(Body::Xml(xml::to_xml(&mr_data))),
+    }
+}
+
+fn parse_season(store: &Store, season: &str) -> Result<u16, ApiError> {
+    match season {
+        "current" => store
+            .seasons()
+            .last()
+            .copied()
+            .ok_or_else(|| ApiError::not_found("no season served")),
+        _ => season
+            .parse()
+            .map_err(|_| ApiError::bad_request(format!("invalid season: `{season}`"))),
+    }
+}
+
+// Rounds are 1-based positions in the season's race summary
+fn selected_rounds(races: &RaceSummary, round: Option<&str>) -> Result<Vec<usize>, ApiError> {
+    let round = match round {
+        None => return Ok((1..=races.data.len()).collect()),
+        Some("last") => races.data.len(),
+        Some(round) => round
+            .parse()
+            .map_err(|_| ApiError::bad_request(format!("invalid round: `{round}`")))?,
+    };
+    if round == 0 || round > races.data.len() {
+        return Err(ApiError::not_found(format!(
+            "no round `{round}` in season `{}`",
+            races.year
+        )));
+    }
+    Ok(vec![round])
+}
+
+fn races(
+    store: &Store,
+    season: u16,
+    round: Option<&str>,
+    page: &Page,
+    url: &str,
+) -> Result<Value, ApiError> {
+    let summary = store.races(season)?;
+    let rounds = selected_rounds(&summary, round)?;
+
+    let mut races = vec![];
+    for (idx, round) in rounds.iter().enumerate() {
+        if page.contains(idx) {
+            races.push(race(season, *round, &summary.data[round - 1], None)?);
+        }
+    }
+
+    let mut table = Map::new();
+    table.insert("season".into(), json!(season.to_string()));
+    if let Some(round) = round {
+        table.insert("round".into(), json!(round));
+    }
+    table.insert("Races".into(), json!(races));
+    Ok(page.mr_data(url, rounds.len(), "RaceTable", table))
+}
+
+fn results(
+    store: &Store,
+    season: u16,
+    round: Option<&str>,
+    page: &Page,
+    url: &str,
+) -> Result<Value, ApiError> {
+    let summary = store.races(season)?;
+    let rounds = selected_rounds(&summary, round)?;
+
+    // pagination applies to result rows, which are grouped back by race
+    let mut races = vec![];
+    let mut total = 0;
+    for round in rounds {
+        let gp = &summary.data[round - 1];
+        let race_result = store.race_result(season, circuit(gp)?.idx)?;
+
+        let mut rows = vec![];
+        for (idx, entry) in race_result.data.iter().enumerate() {
+            if page.contains(total) {
+                rows.push(result(idx, entry));
+            }
+            total += 1;
+        }
+        if !rows.is_empty() {
+            let mut race = race(season, round, gp, race_result.venue.as_ref())?;
+            race["Results"] = json!(rows);
+            races.push(race);
+        }
+    }
+
+    let mut table = Map::new();
+    table.insert("season".into(), json!(season.to_string()));
+    if let Some(round) = round {
+        table.insert("round".into(), json!(round));
+    }
+    table.insert("Races".into(), json!(races));
+    Ok(page.mr_data(url, total, "RaceTable", table))
+}
+
+fn driver_standings(store: &Store, season: u16, page: &Page, url: &str) -> Result<Value, ApiError> {
+    let summary = store.drivers(season)?;
+    let races = store.races(season)?;
+
+    let mut standings = vec![];
+    for (idx, entry) in summary.data.iter().enumerate() {
+        if !page.contains(idx) {
+            continue;
+        }
+        let wins = races
+            .data
+            .iter()
+            .filter(|gp| gp.winner_name.is_same(&entry.driver_name))
+            .count();
+
+        let mut driver = driver(&entry.driver_name);
+        driver["url"] = json!(format!("https://www.formula1.com{}", entry.url));
+        driver["nationality"] = json!(entry.nationality);
+
+        standings.push(json!({
+            "position": position(idx, &entry.pos),
+            "positionText": entry.pos,
+            "points": entry.pts,
+            "wins": wins.to_string(),
+            "Driver": driver,
+            "Constructors": [constructor(&entry.car)],
+        }));
+    }
+
+    let list = json!({
+        "season": season.to_string(),
+        "round": races.data.len().to_string(),
+        "DriverStandings": standings,
+    });
+    let mut table = Map::new();
+    table.insert("season".into(), json!(season.to_string()));
+    table.insert("StandingsLists".into(), json!([list]));
+    Ok(page.mr_data(url, summary.data.len(), "StandingsTable", table))
+}
+
+fn constructor_standings(
+    store: &Store,
+    season: u16,
+    page: &Page,
+    url: &str,
+) -> Result<Value, ApiError> {
+    let summary = store.teams(season)?;
+    let races = store.races(season)?;
+
+    let mut standings = vec![];
+    for (idx, entry) in summary.data.iter().enumerate() {
+        if !page.contains(idx) {
+            continue;
+        }
+        let wins = races.data.iter().filter(|gp| gp.car == entry.team).count();
+        let mut constructor = constructor(&entry.team);
+        constructor["url"] = json!(format!("https://www.formula1.com{}", entry.url));
+
+        standings.push(json!({
+            "position": position(idx, &entry.pos),
+            "positionText": entry.pos,
+            "points": entry.pts,
+            "wins": wins.to_string(),
+            "Constructor": constructor,
+        }));
+    }
+
+    let list = json!({
+        "season": season.to_string(),
+        "round": races.data.len().to_string(),
+        "ConstructorStandings": standings,
+    });
+    let mut table = Map::new();
+    table.insert("season".into(), json!(season.to_string()));
+    table.insert("StandingsLists".into(), json!([list]));
+    Ok(page.mr_data(url, summary.data.len(), "StandingsTable", table))
+}
+
+fn fastest_laps(store: &Store, season: u16, page: &Page, url: &str) -> Result<Value, ApiError> {
+    let summary = store.fastest_laps(season)?;
+    let races = store.races(season)?;
+
+    let mut table_races = vec![];
+    for (idx, entry) in summary.data.iter().enumerate() {
+        if !page.contains(idx) {
+            continue;
+        }
+        // both summaries follow the calendar order, but a Grand Prix name can appear
+        // more than once in a season so prefer the race at the same index
+        let same_name = |gp: &RaceSummaryEntry| {
+            gp.grand_prix.trim().to_lowercase() == entry.grand_prix.trim().to_lowercase()
+        };
+        let Some(race_idx) = races
+            .data
+            .get(idx)
+            .filter(|gp| same_name(gp))
+            .map(|_| idx)
+            .or_else(|| races.data.iter().position(same_name))
+        else {
+            return Err(ApiError::upstream(anyhow::anyhow!(
+                "find race for fastest lap of round `{}`: {}",
+                idx + 1,
+                entry.grand_prix
+            )));
+        };
+        let round = race_idx + 1;
+        let gp = &races.data[race_idx];
+
+        let mut race = race(season, round, gp, None)?;
+        race["Results"] = json!([{
+            "Driver": driver(&entry.driver_name),
+            "Constructor": constructor(&entry.car),
+            "FastestLap": {
+                "rank": "1",
+                "Time": { "time": entry.time },
+            },
+        }]);
+        table_races.push(race);
+    }
+
+    let mut table = Map::new();
+    table.insert("season".into(), json!(season.to_string()));
+    table.insert("Races".into(), json!(table_races));
+    Ok(page.mr_data(url, summary.data.len(), "RaceTable", table))
+}
+
+fn circuit(gp: &RaceSummaryEntry) -> Result<Circuit, ApiError> {
+    gp.circuit().map_err(ApiError::upstream)
+}
+
+// The venue is only known from the race result page, the location is left out
+// without it
+fn race(
+    season: u16,
+    round: usize,
+    gp: &RaceSummaryEntry,
+    venue: Option<&Venue>,
+) -> Result<Value, ApiError> {
+    let circuit = circuit(gp)?;
+    // Example: 05 Mar 2023
+    let date = NaiveDate::parse_from_str(gp.date.trim(), "%d %b %Y")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| gp.date.clone());
+
+    let mut race = json!({
+        "season": season.to_string(),
+        "round": round.to_string(),
+        "url": format!("https://www.formula1.com{}", gp.url),
+        "raceName": format!("{} Grand Prix", gp.grand_prix),
+        "Circuit": {
+            "circuitId": circuit.name,
+            "circuitName": circuit.display_name,
+        },
+        "date": date,
+    });
+    if let Some(venue) = venue {
+        race["Circuit"]["circuitName"] = json!(venue.circuit);
+        race["Circuit"]["Location"] = json!({ "locality": venue.location });
+    }
+    Ok(race)
+}
+
+fn result(idx: usize, entry: &RaceResultEntry) -> Value {
+    let mut result = json!({
+        "number": entry.no,
+        "position": (idx + 1).to_string(),
+        "positionText": position_text(entry),
+        "points": entry.pts,
+        "Driver": driver(&entry.driver_name),
+        "Constructor": constructor(&entry.car),
+        "laps": entry.laps,
+        "status": status(entry),
+    });
+
+    // the winner has the race time, the other finishers the gap to the winner
+    let time = entry.time_retired.trim();
+    if entry.position().is_some() && !entry.is_dnf() && !time.contains("lap") {
+        result["Time"] = json!({ "time": time.trim_end_matches('s') });
+    }
+    result
+}
+
+fn position(idx: usize, pos: &str) -> String {
+    match pos.trim().parse::<u32>() {
+        Ok(pos) => pos.to_string(),
+        Err(_) => (idx + 1).to_string(),
+    }
+}
+
+fn position_text(entry: &RaceResultEntry) -> String {
+    if entry.position().is_some() {
+        return entry.pos.trim().to_string();
+    }
+    let text = match entry.pos.trim().to_uppercase().as_str() {
+        "DQ" => "D",
+        "EX" => "E",
+        "DNS" => "W",
+        "DNQ" | "DNPQ" => "F",
+        _ if entry.is_dnf() => "R",
+        _ => "N",
+    };
+    text.to_string()
+}
+
+fn status(entry: &RaceResultEntry) -> String {
+    let time = entry.time_retired.trim();
+    let status = match entry.pos.trim().to_uppercase().as_str() {
+        "DQ" => "Disqualified",
+        "EX" => "Excluded",
+        "DNS" => "Did not start",
+        "DNQ" => "Did not qualify",
+        "DNPQ" => "Did not prequalify",
+        _ if entry.is_dnf() => "Retired",
+        // Example: +1 lap, +2 laps
+        _ if time.starts_with('+') && time.contains("lap") => {
+            return time.replace("lap", "Lap");
+        }
+        _ if entry.position().is_some() => "Finished",
+        _ => "Not classified",
+    };
+    status.to_string()
+}
+
+fn driver(name: &DriverName) -> Value {
+    let mut driver = json!({
+        "driverId": driver_id(name),
+        "givenName": name.first,
+        "familyName": name.last,
+    });
+    if let Some(code) = &name.code {
+        driver["code"] = json!(code);
+    }
+    driver
+}
+
+fn constructor(car: &str) -> Value {
+    json!({
+        "constructorId": constructor_id(car),
+        "name": car,
+    })
+}
+
+// Ids are derived from the names only, so that the standings and the results
+// of a driver or a constructor share the same id
+fn driver_id(name: &DriverName) -> String {
+    slug(&name.full_name())
+}
+
+fn constructor_id(name: &str) -> String {
+    slug(name)
+}
+
+struct Page {
+    offset: usize,
+    limit: usize,
+}
+
+impl Page {
+    fn parse(req: &Request) -> Result<Self, ApiError> {
+        let mut page = Self {
+            offset: 0,
+            limit: DEFAULT_LIMIT,
+        };
+        for (key, value) in &req.query {
+            let parse = || {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| ApiError::bad_request(format!("invalid {key}: `{value}`")))
+            };
+            match key.as_str() {
+                "offset" => page.offset = parse()?,
+                "limit" => page.limit = parse()?.min(MAX_LIMIT),
+                // Ergast ignores the parameters it doesn't know about (e.g. `callback`)
+                _ => {}
+            }
+        }
+        Ok(page)
+    }
+
+    fn contains(&self, idx: usize) -> bool {
+        idx >= self.offset && idx < self.offset + self.limit
+    }
+
+    fn mr_data(
+        &self,
+        url: &str,
+        total: usize,
+        table_name: &str,
+        table: Map<String, Value>,
+    ) -> Value {
+        let mut mr_data = Map::new();
+        mr_data.insert("xmlns".into(), json!(XMLNS));
+        mr_data.insert("series".into(), json!("f1"));
+        mr_data.insert("url".into(), json!(url));
+        mr_data.insert("limit".into(), json!(self.limit.to_string()));
+        mr_data.insert("offset".into(), json!(self.offset.to_string()));
+        mr_data.insert("total".into(), json!(total.to_string()));
+        mr_data.insert(table_name.into(), Value::Object(table));
+        json!({ "MRData": mr_data })
+    }
+}
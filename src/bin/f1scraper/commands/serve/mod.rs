@@ -0,0 +1,174 @@
+use std::fmt;
+use std::time::Duration;
+
+use log::{info, warn};
+use serde_json::{json, Value};
+use tiny_http::{Header, Response, Server};
+
+use crate::commands::{ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+use store::Store;
+
+mod api;
+mod ergast;
+mod store;
+mod xml;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+
+    /// Seconds the pages of the latest season are served from the cache before
+    /// being scraped again, older seasons are cached for good
+    #[arg(long, value_name = "SECS", default_value_t = 300)]
+    ttl: u64,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    let latest = scrape_ctx.seasons().latest;
+    let store =
+        Store::new(&scrape_ctx, year_min, year_max).with_ttl(latest, Duration::from_secs(args.ttl));
+
+    let server = Server::http(&args.addr)
+        .map_err(|err| anyhow::anyhow!("listen on `{}`: {}", args.addr, err))?;
+    info!("Listening on http://{}", args.addr);
+
+    for request in server.incoming_requests() {
+        let (status, body) = match Request::parse(&request) {
+            Ok(req) => match route(&store, &req) {
+                Ok(body) => (200, body),
+                Err(err) => err.into_response(),
+            },
+            Err(err) => err.into_response(),
+        };
+        info!("{} {} -> {}", request.method(), request.url(), status);
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes("Content-Type", body.content_type())
+                    .expect("static header is valid"),
+            );
+        if let Err(err) = request.respond(response) {
+            warn!("write response: {}", err);
+        }
+    }
+    Ok(())
+}
+
+fn route(store: &Store, req: &Request) -> Result<Body, ApiError> {
+    if req.has_prefix(&ergast::PREFIX) {
+        return ergast::handle(store, req);
+    }
+    api::handle(store, req).map(Body::Json)
+}
+
+pub enum Body {
+    Json(Value),
+    Xml(String),
+}
+
+impl Body {
+    fn content_type(&self) -> &'static str {
+        match self {
+            Body::Json(_) => "application/json",
+            Body::Xml(_) => "application/xml",
+        }
+    }
+}
+
+impl fmt::Display for Body {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Body::Json(value) => write!(f, "{value}"),
+            Body::Xml(xml) => write!(f, "{xml}"),
+        }
+    }
+}
+
+pub struct Request {
+    pub path: String,
+    pub segments: Vec<String>,
+    pub query: Vec<(String, String)>,
+}
+
+impl Request {
+    fn parse(request: &tiny_http::Request) -> Result<Self, ApiError> {
+        if *request.method() != tiny_http::Method::Get {
+            return Err(ApiError::new(405, "only GET requests are supported"));
+        }
+        let url = reqwest::Url::parse("http://localhost")
+            .and_then(|base| base.join(request.url()))
+            .map_err(|err| ApiError::bad_request(format!("invalid url: {err}")))?;
+        let segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+        let query = url
+            .query_pairs()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Ok(Self {
+            path: url.path().to_string(),
+            segments,
+            query,
+        })
+    }
+
+    fn has_prefix(&self, prefix: &[&str]) -> bool {
+        self.segments.len() >= prefix.len() && self.segments.iter().zip(prefix).all(|(s, p)| s == p)
+    }
+}
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new<S: Into<String>>(status: u16, message: S) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request<S: Into<String>>(message: S) -> Self {
+        Self::new(400, message)
+    }
+
+    pub fn not_found<S: Into<String>>(message: S) -> Self {
+        Self::new(404, message)
+    }
+
+    pub fn internal(err: anyhow::Error) -> Self {
+        Self::new(500, format!("{err:#}"))
+    }
+
+    // The page could not be scraped or parsed
+    pub fn upstream(err: anyhow::Error) -> Self {
+        Self::new(502, format!("{err:#}"))
+    }
+
+    fn into_response(self) -> (u16, Body) {
+        warn!("{}", self);
+        (self.status, Body::Json(json!({ "error": self.message })))
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.status, self.message)
+    }
+}
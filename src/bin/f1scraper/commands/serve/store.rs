@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use f1scraper::scrape::{
+    DriverResultSummaryTarget, FastestLapResultSummaryTarget, QualifyingResultTarget,
+    RaceResultSummaryTarget, RaceResultTarget, ScrapeTarget, TeamResultSummaryTarget,
+};
+use f1scraper::types::{
+    Circuit, DriverSummary, FastestLapSummary, QualifyingResult, RaceResult, RaceSummary,
+    TeamSummary,
+};
+
+use crate::commands::ScrapeContext;
+use crate::prelude::*;
+
+use super::ApiError;
+
+// Data access for the server, every page goes through the scraper's response cache
+pub struct Store<'a> {
+    scrape_ctx: &'a ScrapeContext,
+    year_min: u16,
+    year_max: u16,
+    // the pages of the seasons from this one on are scraped again once `ttl` old,
+    // their results being published and corrected as they go
+    live_year: u16,
+    ttl: Duration,
+}
+
+impl<'a> Store<'a> {
+    pub fn new(scrape_ctx: &'a ScrapeContext, year_min: u16, year_max: u16) -> Self {
+        Self {
+            scrape_ctx,
+            year_min,
+            year_max,
+            live_year: u16::MAX,
+            ttl: Duration::MAX,
+        }
+    }
+
+    pub fn with_ttl(mut self, live_year: u16, ttl: Duration) -> Self {
+        self.live_year = live_year;
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn seasons(&self) -> Vec<u16> {
+        (self.year_min..=self.year_max).collect()
+    }
+
+    pub fn races(&self, year: u16) -> Result<RaceSummary, ApiError> {
+        self.check_year(year)?;
+        self.fetch(
+            year,
+            "race result summary",
+            RaceResultSummaryTarget::new(year),
+        )
+    }
+
+    pub fn circuit(&self, year: u16, idx: u16) -> Result<Circuit, ApiError> {
+        let races = self.races(year)?;
+        for gp in &races.data {
+            let circuit = gp
+                .circuit()
+                .with_context(|| {
+                    format!(
+                        "obtain circuit infos from summary data (circuit: `{}`)",
+                        gp.grand_prix
+                    )
+                })
+                .map_err(ApiError::upstream)?;
+            if circuit.idx == idx {
+                return Ok(circuit);
+            }
+        }
+        Err(ApiError::not_found(format!(
+            "no race `{idx}` in season `{year}`"
+        )))
+    }
+
+    pub fn race_result(&self, year: u16, idx: u16) -> Result<RaceResult, ApiError> {
+        let circuit = self.circuit(year, idx)?;
+        self.fetch(year, "race result", RaceResultTarget::new(year, &circuit))
+    }
+
+    pub fn qualifying(&self, year: u16, idx: u16) -> Result<QualifyingResult, ApiError> {
+        let circuit = self.circuit(year, idx)?;
+        self.fetch(
+            year,
+            "qualifying result",
+            QualifyingResultTarget::new(year, &circuit),
+        )
+    }
+
+    pub fn drivers(&self, year: u16) -> Result<DriverSummary, ApiError> {
+        self.check_year(year)?;
+        self.fetch(
+            year,
+            "driver result summary",
+            DriverResultSummaryTarget::new(year),
+        )
+    }
+
+    pub fn teams(&self, year: u16) -> Result<TeamSummary, ApiError> {
+        self.check_year(year)?;
+        self.fetch(
+            year,
+            "team result summary",
+            TeamResultSummaryTarget::new(year),
+        )
+    }
+
+    pub fn fastest_laps(&self, year: u16) -> Result<FastestLapSummary, ApiError> {
+        self.check_year(year)?;
+        self.fetch(
+            year,
+            "fastest lap result summary",
+            FastestLapResultSummaryTarget::new(year),
+        )
+    }
+
+    fn fetch<T: ScrapeTarget>(
+        &self,
+        year: u16,
+        what: &str,
+        target: Result<T>,
+    ) -> Result<T::Output, ApiError> {
+        let target = target
+            .with_context(|| format!("create scrape target: {what} {year}"))
+            .map_err(ApiError::upstream)?;
+        let scraper = &self.scrape_ctx.scraper;
+        let output = match year >= self.live_year {
+            true => scraper.fetch_within(target, self.ttl),
+            false => scraper.fetch(target),
+        };
+        output
+            .with_context(|| format!("scrape: {what} {year}"))
+            .map_err(ApiError::upstream)
+    }
+
+    fn check_year(&self, year: u16) -> Result<(), ApiError> {
+        if year < self.year_min || year > self.year_max {
+            return Err(ApiError::not_found(format!(
+                "season `{year}` not served: available seasons are {} to {}",
+                self.year_min, self.year_max
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+// Fields written as attributes, the other ones are child elements named after
+// the capitalized field (e.g. `raceName` -> `<RaceName>`)
+const ATTRIBUTES: [&str; 18] = [
+    "xmlns",
+    "series",
+    "url",
+    "limit",
+    "offset",
+    "total",
+    "season",
+    "round",
+    "circuitId",
+    "driverId",
+    "code",
+    "constructorId",
+    "number",
+    "position",
+    "positionText",
+    "points",
+    "wins",
+    "rank",
+];
+
+// Render an Ergast JSON response in Ergast's XML shape
+// Example: `{"MRData": {"RaceTable": {"Races": [{"round": "1", "raceName": ".."}]}}}`
+//   -> `<MRData><RaceTable><Race round="1"><RaceName>..</RaceName></Race></RaceTable></MRData>`
+pub fn to_xml(value: &Value) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    if let Value::Object(fields) = value {
+        for (name, value) in fields {
+            element(&mut xml, name, value);
+        }
+    }
+    xml
+}
+
+fn element(xml: &mut String, name: &str, value: &Value) {
+    let name = capitalize(name);
+    match value {
+        // Example: `Races` -> `<Race>`, results are wrapped in a `<ResultsList>`
+        Value::Array(items) => {
+            let (wrapper, item) = match name.as_str() {
+                "Results" => (Some("ResultsList"), "Result"),
+                _ => (None, name.strip_suffix('s').unwrap_or(&name)),
+            };
+            if let Some(wrapper) = wrapper {
+                xml.push_str(&format!("<{wrapper}>"));
+            }
+            for value in items {
+                element(xml, item, value);
+            }
+            if let Some(wrapper) = wrapper {
+                xml.push_str(&format!("</{wrapper}>"));
+            }
+        }
+        Value::Object(fields) => {
+            xml.push_str(&format!("<{name}"));
+            for (key, value) in fields.iter().filter(|(k, _)| is_attribute(k)) {
+                xml.push_str(&format!(" {key}=\"{}\"", escape(&text(value))));
+            }
+            xml.push('>');
+            for (key, value) in fields.iter().filter(|(k, _)| !is_attribute(k)) {
+                // Example: `"Time": {"time": "1:33:56.736"}` -> `<Time>1:33:56.736</Time>`
+                match (name.as_str(), key.as_str()) {
+                    ("Time", "time") => xml.push_str(&escape(&text(value))),
+                    _ => element(xml, key, value),
+                }
+            }
+            xml.push_str(&format!("</{name}>"));
+        }
+        _ => xml.push_str(&format!("<{name}>{}</{name}>", escape(&text(value)))),
+    }
+}
+
+fn is_attribute(key: &str) -> bool {
+    ATTRIBUTES.contains(&key)
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,61 @@
+use f1scraper::points::{PointsSystem, Standings};
+
+use crate::commands::{fastestlap, race, ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The points system to apply, named after a season it was used in (e.g. 2010).
+    /// Defaults to the system of the simulated season
+    #[arg(short, long)]
+    system: Option<PointsSystem>,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    for year in year_min..=year_max {
+        Standings::check_season(year)?;
+        let system = match &args.system {
+            Some(system) => system.clone(),
+            None => PointsSystem::for_year(year)?,
+        };
+
+        // the standings of a season missing races would be misleading
+        let races = scrape_ctx.attempt(year, "race results", || {
+            race::result::query_season(&scrape_ctx.scraper, year)
+        });
+        let Some(races) = races? else {
+            continue;
+        };
+        let fastest_laps = match system.fastest_lap {
+            Some(_) => {
+                let fastest_laps = scrape_ctx.attempt(year, "fastest lap summary", || {
+                    fastestlap::summary::query_and_parse(&scrape_ctx.scraper, year)
+                });
+                match fastest_laps? {
+                    Some(fastest_laps) => Some(fastest_laps),
+                    None => continue,
+                }
+            }
+            None => None,
+        };
+
+        let standings = Standings::simulate(&system, &races, fastest_laps.as_ref())?;
+        print(&standings)?
+    }
+    Ok(())
+}
+
+fn print(standings: &Standings) -> Result<()> {
+    let prefix = format!("[{}][system {}]", standings.year, standings.system);
+    for row in standings.drivers.iter() {
+        println!("{prefix}[drivers] {row:?}");
+    }
+    for row in standings.constructors.iter() {
+        println!("{prefix}[constructors] {row:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use log::info;
+
+use f1scraper::lineage::{Constructor, Lineage};
+use f1scraper::types::TeamSummaryEntry;
+
+use crate::commands::{ScrapeContext, YearFlags};
+use crate::prelude::*;
+
+use super::summary;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The constructor id, or the name of one of its eras (e.g. `enstone`, `benetton`)
+    constructor_name: String,
+
+    #[command(flatten)]
+    year_flags: YearFlags,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    let lineage = Lineage::builtin();
+    let constructor = lineage.find(&args.constructor_name)?;
+
+    // only the seasons the constructor raced in are scraped
+    let years: Vec<_> = (year_min..=year_max)
+        .filter(|year| constructor.contains(*year))
+        .collect();
+    scrape_ctx.progress.add_targets(years.len());
+
+    let mut pts = 0.0;
+    for year in years {
+        let summary = scrape_ctx.attempt(year, "team summary", || {
+            summary::query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(summary) = summary? else {
+            continue;
+        };
+
+        for entry in &summary.data {
+            let team = entry.team()?;
+            let Some((found, era)) = lineage.resolve(year, &team) else {
+                continue;
+            };
+            if found.id != constructor.id {
+                continue;
+            }
+            info!("[{}] found team: {:?} ({})", year, team, era.name);
+            pts += entry.pts.trim().parse::<f32>().unwrap_or_default();
+            print(constructor, year, &era.name, entry)?
+        }
+    }
+    println!(
+        "[{} ({})][total] pts: {pts}",
+        constructor.name, constructor.id
+    );
+    Ok(())
+}
+
+fn print(constructor: &Constructor, year: u16, era: &str, entry: &TeamSummaryEntry) -> Result<()> {
+    println!(
+        "[{} ({})][{year}][{era}] {entry:?}",
+        constructor.name, constructor.id
+    );
+    Ok(())
+}
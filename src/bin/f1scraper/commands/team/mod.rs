@@ -2,8 +2,9 @@ use crate::prelude::*;
 
 use super::ScrapeContext;
 
+mod history;
 mod result;
-mod summary;
+pub mod summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
@@ -18,11 +19,15 @@ pub enum Commands {
 
     /// Scrape race results
     Result(result::Args),
+
+    /// Scrape the seasons of a constructor under all of its names
+    History(history::Args),
 }
 
 pub fn process(scrape_ctx: ScrapeContext, cmd: Commands) -> Result<()> {
     match cmd {
         Commands::Summary(args) => summary::run(scrape_ctx, args),
         Commands::Result(args) => result::run(scrape_ctx, args),
+        Commands::History(args) => history::run(scrape_ctx, args),
     }
 }
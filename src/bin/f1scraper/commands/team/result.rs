@@ -1,6 +1,3 @@
-use log::debug;
-use std::collections::HashMap;
-
 use f1scraper::scrape::{Scraper, TeamResultTarget};
 use f1scraper::types::{Team, TeamResult};
 
@@ -11,53 +8,49 @@ use super::summary;
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
-    /// The name of the Grand Prix
-    team_name: Option<String>,
+    /// The names of the teams, or glob patterns (e.g. `red*`)
+    team_names: Vec<String>,
 
     #[command(flatten)]
     year_flags: YearFlags,
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary and one selection per season, the entities they list are added
+    // on the way
+    scrape_ctx
+        .progress
+        .add_targets(2 * (usize::from(year_max - year_min) + 1));
 
     for year in year_min..=year_max {
         // query summary to obtain the list of available teams
-        let summary = summary::query_and_parse(&scrape_ctx.scraper, year)?;
-
-        // retrieve teams
-        // index by name
-        let mut team_by_name = HashMap::new();
-        let mut team_by_display_name = HashMap::new();
-        for gp in &summary.data {
-            let team = gp.team().with_context(|| {
-                format!("obtain team infos from summary data (team: `{}`)", gp.team)
-            })?;
-            team_by_name.insert(team.name.clone().trim().to_lowercase(), team.clone());
-            team_by_display_name.insert(team.display_name.clone().trim().to_lowercase(), team);
-        }
-        debug!("team index: {:?}", team_by_name);
+        let resolver = scrape_ctx.attempt(year, "team summary", || {
+            summary::query_and_parse(&scrape_ctx.scraper, year).and_then(|summary| summary.teams())
+        });
+        let Some(resolver) = resolver? else {
+            continue;
+        };
 
-        // all teams
-        let mut teams: Vec<_> = team_by_name.values().collect();
-
-        // if argument passed, filter by team name
-        if let Some(team_name) = &args.team_name {
-            let team_name = &team_name.trim().to_lowercase();
-            let team_name_indexes = vec![&team_by_name, &team_by_display_name];
-            let team = team_name_indexes
-                .iter()
-                .filter_map(|index| index.get(team_name))
-                .next()
-                .ok_or(anyhow::anyhow!(
-                    "find team for year `{}` with name: {}",
-                    year,
-                    team_name
-                ))?;
-            teams = vec![team];
-        }
-        for team in teams {
-            let team_result = query_and_parse(&scrape_ctx.scraper, year, team)?;
+        // teams matching the names, all if none passed, a name can be missing
+        // from some seasons
+        let teams = scrape_ctx.attempt(year, "team selection", || {
+            resolver
+                .select(&args.team_names)
+                .with_context(|| format!("resolve teams for year `{year}`"))
+        });
+        let Some(teams) = teams? else {
+            continue;
+        };
+        scrape_ctx.progress.add_targets(teams.len());
+        for (_, team) in teams {
+            let target = format!("team result {}", team.display_name);
+            let team_result = scrape_ctx.attempt(year, &target, || {
+                query_and_parse(&scrape_ctx.scraper, year, team)
+            });
+            let Some(team_result) = team_result? else {
+                continue;
+            };
             print(&team_result)?
         }
     }
@@ -68,14 +61,10 @@ fn query_and_parse(scraper: &Scraper, year: u16, team: &Team) -> Result<TeamResu
     // create scrape target
     let target = TeamResultTarget::new(year, team)
         .with_context(|| format!("create scrape target: team result {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: team result {year}"))?;
-
-    // parse html text as team result
-    let team_result = TeamResult::parse(&html, year, &team.clone())?;
-    Ok(team_result)
+    // run scrape and parse html text as team result
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: team result {year}"))
 }
 
 fn print(team_result: &TeamResult) -> Result<()> {
@@ -11,9 +11,18 @@ pub struct Args {
 }
 
 pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
-    let (year_min, year_max) = args.year_flags.min_max();
+    let (year_min, year_max) = args.year_flags.min_max(&scrape_ctx)?;
+    // one summary per season
+    scrape_ctx
+        .progress
+        .add_targets(usize::from(year_max - year_min) + 1);
     for year in year_min..=year_max {
-        let result = query_and_parse(&scrape_ctx.scraper, year)?;
+        let result = scrape_ctx.attempt(year, "team summary", || {
+            query_and_parse(&scrape_ctx.scraper, year)
+        });
+        let Some(result) = result? else {
+            continue;
+        };
         print(&result)?
     }
     Ok(())
@@ -23,13 +32,10 @@ pub fn query_and_parse(scraper: &Scraper, year: u16) -> Result<TeamSummary> {
     // create scrape target
     let target = TeamResultSummaryTarget::new(year)
         .with_context(|| format!("create scrape target: team result summary {year}"))?;
-    // run scrape
-    let html = scraper
-        .scrape(target)
-        .with_context(|| format!("scrape: team result summary {year}"))?;
-    // parse html text as team summary
-    let summaries = TeamSummary::parse(&html, year)?;
-    Ok(summaries)
+    // run scrape and parse html text as team summary
+    scraper
+        .fetch(target)
+        .with_context(|| format!("scrape: team result summary {year}"))
 }
 
 fn print(summaries: &TeamSummary) -> Result<()> {
@@ -0,0 +1,74 @@
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use f1scraper::watch::{Watch, WatchEvent, Webhook};
+
+use crate::commands::{diff, ScrapeContext};
+use crate::prelude::*;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The season to watch (defaults to the latest season)
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Seconds to wait between two polls
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+
+    /// Post the change events as JSON to this URL instead of printing them
+    #[arg(long, value_name = "URL")]
+    webhook: Option<String>,
+
+    /// Print the change events as JSON, one per line
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(scrape_ctx: ScrapeContext, args: Args) -> Result<()> {
+    let seasons = scrape_ctx.seasons();
+    let year = match args.year {
+        Some(year) => seasons.check(year)?,
+        None => seasons.latest,
+    };
+    let webhook = args.webhook.as_deref().map(Webhook::new).transpose()?;
+
+    let mut watch = Watch::new(year);
+    loop {
+        let first = watch.rows().is_none();
+        let poll = watch.poll(&scrape_ctx.scraper);
+        // when keeping going, the failed pages are polled again on the next poll
+        for failure in poll.failures {
+            match scrape_ctx.failures.keep_going() {
+                true => warn!("poll season {year}: {failure}"),
+                false => return Err(failure.into()),
+            }
+        }
+        match poll.event {
+            Some(event) => emit(&event, webhook.as_ref(), args.json)?,
+            None if first && watch.rows().is_some() => {
+                let rows = watch.rows().map_or(0, <[_]>::len);
+                info!("Watching {rows} result rows of season {year}");
+            }
+            None => debug!("No changes in season {year}"),
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+fn emit(event: &WatchEvent, webhook: Option<&Webhook>, json: bool) -> Result<()> {
+    if let Some(webhook) = webhook {
+        // keep watching, the event is printed instead
+        let Err(err) = webhook.send(event) else {
+            return Ok(());
+        };
+        warn!("{err:#}");
+    }
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+        return Ok(());
+    }
+    diff::print(&event.changes, false)
+}
@@ -17,6 +17,9 @@ struct Cli {
     #[command(subcommand)]
     command: commands::Commands,
 
+    #[command(flatten)]
+    global_flags: commands::GlobalFlags,
+
     /// Enable info(-v), debug(-vv) or trace(-vvv) logging
     #[arg(long, short = 'v', action = clap::ArgAction::Count)]
     verbose: u8,
@@ -54,5 +57,6 @@ fn main() -> Result<()> {
 
     // Run command
     let cmd_name = cli.command.to_string();
-    commands::process(cli.command).with_context(|| format!("process command `{cmd_name}`"))
+    commands::process(cli.command, cli.global_flags)
+        .with_context(|| format!("process command `{cmd_name}`"))
 }
@@ -0,0 +1,130 @@
+use crate::registry::DriverRegistry;
+use crate::types::{DriverFragment, DriverResult, DriverSummaryEntry, QualifyingResult};
+
+#[derive(Default, Debug)]
+pub struct Career {
+    pub driver: DriverFragment,
+    pub seasons: Vec<CareerSeason>,
+}
+
+#[derive(Default, Debug)]
+pub struct CareerSeason {
+    pub year: u16,
+    pub championship_pos: Option<u32>,
+    pub pts: f32,
+    pub starts: u32,
+    pub wins: u32,
+    pub podiums: u32,
+    pub dnfs: u32,
+    // `None` when no qualifying result was scraped for the season
+    pub poles: Option<u32>,
+    pub teams: Vec<String>,
+}
+
+#[derive(Default, Debug)]
+pub struct CareerTotals {
+    pub seasons: u32,
+    pub starts: u32,
+    pub wins: u32,
+    pub podiums: u32,
+    pub dnfs: u32,
+    pub poles: Option<u32>,
+    pub pts: f32,
+    pub championships: u32,
+    pub best_championship_pos: Option<u32>,
+    pub teams: Vec<String>,
+}
+
+impl Career {
+    pub fn new(driver: &DriverFragment) -> Self {
+        Self {
+            driver: driver.clone(),
+            seasons: vec![],
+        }
+    }
+
+    // Add a season from the driver's standings row and race by race results.
+    //
+    // Poles are counted from `qualifying`, which may cover only some of the races,
+    // the pole sitters are resolved with the season's `registry`.
+    pub fn add_season(
+        &mut self,
+        standing: &DriverSummaryEntry,
+        results: &DriverResult,
+        qualifying: Option<&[QualifyingResult]>,
+        registry: &DriverRegistry,
+    ) {
+        let mut season = CareerSeason {
+            year: results.year,
+            championship_pos: standing.position(),
+            pts: standing.points(),
+            ..Default::default()
+        };
+
+        for entry in &results.data {
+            if entry.started() {
+                season.starts += 1;
+            }
+            if entry.is_dnf() {
+                season.dnfs += 1;
+            }
+            match entry.position() {
+                Some(1) => {
+                    season.wins += 1;
+                    season.podiums += 1
+                }
+                Some(2..=3) => season.podiums += 1,
+                _ => {}
+            }
+            push_unique(&mut season.teams, &entry.car);
+        }
+        if season.teams.is_empty() {
+            push_unique(&mut season.teams, &standing.car);
+        }
+
+        season.poles = qualifying.filter(|q| !q.is_empty()).map(|qualifying| {
+            qualifying
+                .iter()
+                .filter_map(|q| q.data.first())
+                .filter_map(|pole| registry.resolve(&pole.driver_name))
+                .filter(|driver| driver.id == self.driver.id)
+                .count() as u32
+        });
+
+        self.seasons.push(season);
+    }
+
+    pub fn totals(&self) -> CareerTotals {
+        let mut totals = CareerTotals::default();
+        for season in &self.seasons {
+            totals.seasons += 1;
+            totals.starts += season.starts;
+            totals.wins += season.wins;
+            totals.podiums += season.podiums;
+            totals.dnfs += season.dnfs;
+            totals.pts += season.pts;
+            if let Some(poles) = season.poles {
+                *totals.poles.get_or_insert(0) += poles;
+            }
+            if season.championship_pos == Some(1) {
+                totals.championships += 1;
+            }
+            totals.best_championship_pos =
+                match (totals.best_championship_pos, season.championship_pos) {
+                    (Some(best), Some(pos)) => Some(best.min(pos)),
+                    (best, pos) => best.or(pos),
+                };
+            for team in &season.teams {
+                push_unique(&mut totals.teams, team);
+            }
+        }
+        totals
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    let value = value.trim();
+    if !value.is_empty() && !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
+}
@@ -0,0 +1,6 @@
+mod teammates;
+
+pub use teammates::HeadToHead;
+pub use teammates::HeadToHeadSide;
+pub use teammates::TeamComparison;
+pub use teammates::TeammateReport;
@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::types::{QualifyingResult, RaceResult, RaceResultEntry};
+
+#[derive(Default, Debug)]
+pub struct TeammateReport {
+    pub year: u16,
+    pub teams: Vec<TeamComparison>,
+}
+
+#[derive(Default, Debug)]
+pub struct TeamComparison {
+    pub car: String,
+    pub pairs: Vec<HeadToHead>,
+}
+
+#[derive(Default, Debug)]
+pub struct HeadToHead {
+    // races both drivers took part in for the same car
+    pub races: u32,
+    // races where qualifying results were available for both drivers
+    pub qualifying_sessions: u32,
+    pub first: HeadToHeadSide,
+    pub second: HeadToHeadSide,
+}
+
+#[derive(Default, Debug)]
+pub struct HeadToHeadSide {
+    pub driver: String,
+    pub race_ahead: u32,
+    // `None` when no qualifying result was available for the pairing
+    pub qualifying_ahead: Option<u32>,
+    pub pts: f32,
+    pub points_share: f32,
+    pub dnfs: u32,
+}
+
+impl TeammateReport {
+    // Compare drivers sharing the same car, race by race.
+    //
+    // Qualifying results are matched to races by circuit and are optional: races
+    // without one only contribute to the race head-to-head.
+    pub fn build(races: &[RaceResult], qualifying: &[QualifyingResult]) -> Self {
+        let year = races.first().map(|r| r.year).unwrap_or_default();
+
+        let mut pairs: BTreeMap<(&str, &str, &str), HeadToHead> = BTreeMap::new();
+        for race in races {
+            let qualifying = qualifying
+                .iter()
+                .find(|q| q.year == race.year && q.circuit.idx == race.circuit.idx);
+            let grid: Option<HashMap<&str, usize>> = qualifying.map(|q| {
+                q.data
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, entry)| (entry.driver.as_str(), idx))
+                    .collect()
+            });
+
+            // rows are in classification order, unclassified drivers last
+            let mut by_car: BTreeMap<&str, Vec<Drive>> = BTreeMap::new();
+            for (idx, entry) in race.data.iter().enumerate() {
+                let drives = by_car.entry(&entry.car).or_default();
+                // shared drives list the same driver more than once, the best
+                // placed row counts and the points add up
+                match drives.iter_mut().find(|d| d.driver == entry.driver) {
+                    Some(drive) => drive.pts += entry.points(),
+                    None => drives.push(Drive::new(idx, entry)),
+                }
+            }
+
+            for (car, drives) in by_car {
+                for (i, a) in drives.iter().enumerate() {
+                    for b in drives.iter().skip(i + 1) {
+                        // keep a stable orientation for the pairing across races
+                        let (a, b) = if a.driver <= b.driver { (a, b) } else { (b, a) };
+                        let pair = pairs
+                            .entry((car, a.driver, b.driver))
+                            .or_insert_with(|| HeadToHead::new(a.driver, b.driver));
+                        pair.record_race(a, b);
+
+                        let qualifying_idx = grid
+                            .as_ref()
+                            .and_then(|grid| Some((*grid.get(a.driver)?, *grid.get(b.driver)?)));
+                        if let Some((a_idx, b_idx)) = qualifying_idx {
+                            pair.record_qualifying(a_idx, b_idx);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut teams: Vec<TeamComparison> = vec![];
+        for ((car, _, _), mut pair) in pairs {
+            pair.finish();
+            match teams.last_mut() {
+                Some(team) if team.car == car => team.pairs.push(pair),
+                _ => teams.push(TeamComparison {
+                    car: car.to_string(),
+                    pairs: vec![pair],
+                }),
+            }
+        }
+
+        Self { year, teams }
+    }
+}
+
+impl HeadToHead {
+    fn new(first: &str, second: &str) -> Self {
+        Self {
+            first: HeadToHeadSide::new(first),
+            second: HeadToHeadSide::new(second),
+            ..Default::default()
+        }
+    }
+
+    fn record_race(&mut self, first: &Drive, second: &Drive) {
+        self.races += 1;
+        if first.idx < second.idx {
+            self.first.race_ahead += 1;
+        } else {
+            self.second.race_ahead += 1;
+        }
+        self.first.record(first);
+        self.second.record(second);
+    }
+
+    fn record_qualifying(&mut self, first_idx: usize, second_idx: usize) {
+        self.qualifying_sessions += 1;
+        let (ahead, behind) = if first_idx < second_idx {
+            (&mut self.first, &mut self.second)
+        } else {
+            (&mut self.second, &mut self.first)
+        };
+        *ahead.qualifying_ahead.get_or_insert(0) += 1;
+        behind.qualifying_ahead.get_or_insert(0);
+    }
+
+    fn finish(&mut self) {
+        let total = self.first.pts + self.second.pts;
+        if total > 0.0 {
+            self.first.points_share = self.first.pts / total;
+            self.second.points_share = self.second.pts / total;
+        }
+    }
+}
+
+impl HeadToHeadSide {
+    fn new(driver: &str) -> Self {
+        Self {
+            driver: driver.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, drive: &Drive) {
+        self.pts += drive.pts;
+        if drive.dnf {
+            self.dnfs += 1;
+        }
+    }
+}
+
+// A driver's race for a car
+#[derive(Debug)]
+struct Drive<'a> {
+    // row of the best placed entry
+    idx: usize,
+    driver: &'a str,
+    pts: f32,
+    dnf: bool,
+}
+
+impl<'a> Drive<'a> {
+    fn new(idx: usize, entry: &'a RaceResultEntry) -> Self {
+        Self {
+            idx,
+            driver: &entry.driver,
+            pts: entry.points(),
+            dnf: entry.is_dnf(),
+        }
+    }
+}
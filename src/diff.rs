@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::types::{Circuit, RaceResultEntry};
+
+// One row of a race result dataset, as printed by `race result --format ndjson`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultRow {
+    pub year: u16,
+    pub circuit: Circuit,
+    #[serde(flatten)]
+    pub entry: RaceResultEntry,
+}
+
+impl ResultRow {
+    pub fn new(year: u16, circuit: Circuit, entry: RaceResultEntry) -> Self {
+        Self {
+            year,
+            circuit,
+            entry,
+        }
+    }
+
+    // Read a dataset stored as one JSON row per line, blank lines are skipped
+    pub fn read_all(reader: impl BufRead) -> Result<Vec<Self>> {
+        let mut rows = vec![];
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.with_context(|| format!("read line {}", idx + 1))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = serde_json::from_str(&line)
+                .with_context(|| format!("parse row at line {}", idx + 1))?;
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+}
+
+// A field of a row whose value differs between the datasets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum RowChange {
+    Added {
+        row: ResultRow,
+    },
+    Removed {
+        row: ResultRow,
+    },
+    Changed {
+        year: u16,
+        circuit: Circuit,
+        driver: String,
+        fields: Vec<FieldChange>,
+    },
+}
+
+impl RowChange {
+    pub fn year(&self) -> u16 {
+        match self {
+            RowChange::Added { row } | RowChange::Removed { row } => row.year,
+            RowChange::Changed { year, .. } => *year,
+        }
+    }
+
+    pub fn circuit(&self) -> &Circuit {
+        match self {
+            RowChange::Added { row } | RowChange::Removed { row } => &row.circuit,
+            RowChange::Changed { circuit, .. } => circuit,
+        }
+    }
+}
+
+type RowKey<'r> = (u16, u16, &'r str, usize);
+
+// Key the rows by season, race and driver. Shared drives list a driver more
+// than once in a race, these rows are told apart by their order
+fn keyed(rows: &[ResultRow]) -> Vec<(RowKey<'_>, &ResultRow)> {
+    let mut seen: HashMap<_, usize> = HashMap::new();
+    rows.iter()
+        .map(|row| {
+            let race_driver = (row.year, row.circuit.idx, row.entry.driver.as_str());
+            let occurrence = seen.entry(race_driver).or_default();
+            let key = (race_driver.0, race_driver.1, race_driver.2, *occurrence);
+            *occurrence += 1;
+            (key, row)
+        })
+        .collect()
+}
+
+// Compare two datasets row by row, rows being matched by season, race and
+// driver. Changes follow the order of the old dataset, added rows come last
+pub fn diff_rows(old: &[ResultRow], new: &[ResultRow]) -> Vec<RowChange> {
+    let (old, new) = (keyed(old), keyed(new));
+    let new_by_key: HashMap<_, _> = new.iter().copied().collect();
+    let old_by_key: HashMap<_, _> = old.iter().copied().collect();
+
+    let mut changes = vec![];
+    for (key, old_row) in old {
+        let Some(new_row) = new_by_key.get(&key) else {
+            changes.push(RowChange::Removed {
+                row: old_row.clone(),
+            });
+            continue;
+        };
+        let fields = diff_entries(&old_row.entry, &new_row.entry);
+        if !fields.is_empty() {
+            changes.push(RowChange::Changed {
+                year: old_row.year,
+                circuit: old_row.circuit.clone(),
+                driver: old_row.entry.driver.clone(),
+                fields,
+            });
+        }
+    }
+    for (key, new_row) in new {
+        if !old_by_key.contains_key(&key) {
+            changes.push(RowChange::Added {
+                row: new_row.clone(),
+            });
+        }
+    }
+    changes
+}
+
+fn diff_entries(old: &RaceResultEntry, new: &RaceResultEntry) -> Vec<FieldChange> {
+    let fields = [
+        ("pos", &old.pos, &new.pos),
+        ("no", &old.no, &new.no),
+        ("car", &old.car, &new.car),
+        ("laps", &old.laps, &new.laps),
+        ("time_retired", &old.time_retired, &new.time_retired),
+        ("pts", &old.pts, &new.pts),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange {
+            field,
+            old: old.clone(),
+            new: new.clone(),
+        })
+        .collect()
+}
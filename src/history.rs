@@ -0,0 +1,74 @@
+use crate::resolve::Resolver;
+use crate::types::{Circuit, FastestLapSummaryEntry, QualifyingResult, RaceSummaryEntry, Venue};
+
+#[derive(Default, Debug)]
+pub struct CircuitHistory {
+    pub query: String,
+    pub rows: Vec<CircuitHistoryRow>,
+}
+
+// One race held at the circuit
+#[derive(Default, Debug)]
+pub struct CircuitHistoryRow {
+    pub year: u16,
+    pub circuit: Circuit,
+    // only known when the race result page was scraped
+    pub venue: Option<String>,
+    pub date: String,
+    pub winner: String,
+    pub car: String,
+    // `None` when no qualifying result was available
+    pub pole: Option<String>,
+    pub fastest_lap: Option<String>,
+    pub fastest_lap_time: Option<String>,
+}
+
+impl CircuitHistory {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            rows: vec![],
+        }
+    }
+
+    // Races of a season held at the circuit, matched by their url slug or
+    // display name (e.g. `monaco`, `Great Britain`), or by their venue circuit
+    // name or location (e.g. `silverstone`), like the resolver matches names
+    pub fn matching(&self, races: &[(&Circuit, Option<&Venue>)]) -> Vec<usize> {
+        let mut resolver = Resolver::new("circuit");
+        for (idx, (circuit, venue)) in races.iter().enumerate() {
+            let mut keys = vec![circuit.display_name.as_str(), circuit.name.as_str()];
+            if let Some(venue) = venue {
+                keys.extend([venue.circuit.as_str(), venue.location.as_str()]);
+            }
+            resolver.add(idx, &keys);
+        }
+        resolver.matches(&self.query).into_iter().copied().collect()
+    }
+
+    pub fn add_race(
+        &mut self,
+        year: u16,
+        gp: &RaceSummaryEntry,
+        circuit: &Circuit,
+        venue: Option<&Venue>,
+        qualifying: Option<&QualifyingResult>,
+        fastest_lap: Option<&FastestLapSummaryEntry>,
+    ) {
+        let pole = qualifying
+            .and_then(|q| q.data.iter().find(|entry| entry.position() == Some(1)))
+            .map(|entry| entry.driver.clone());
+
+        self.rows.push(CircuitHistoryRow {
+            year,
+            circuit: circuit.clone(),
+            venue: venue.map(|v| v.circuit.clone()),
+            date: gp.date.clone(),
+            winner: gp.winner.clone(),
+            car: gp.car.clone(),
+            pole,
+            fastest_lap: fastest_lap.map(|entry| entry.driver.clone()),
+            fastest_lap_time: fastest_lap.map(|entry| entry.time.clone()),
+        });
+    }
+}
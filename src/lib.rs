@@ -1,7 +1,19 @@
+pub mod career;
+pub mod compare;
+pub mod diff;
+pub mod history;
+pub mod lineage;
 pub mod parse;
+pub mod points;
+pub mod registry;
+pub mod resolve;
+pub mod schema;
 pub mod scrape;
+pub mod season;
 pub mod types;
+pub mod watch;
 
+#[allow(unused_imports)]
 mod prelude {
     pub use anyhow::{Context, Result};
     pub use log::{debug, info};
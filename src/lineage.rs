@@ -0,0 +1,176 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::resolve::Resolver;
+use crate::types::Team;
+
+const BUILTIN_LINEAGE: &str = include_str!("../data/lineage.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Lineage {
+    pub version: u32,
+    pub constructors: Vec<Constructor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Constructor {
+    pub id: String,
+    pub name: String,
+    pub eras: Vec<ConstructorEra>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConstructorEra {
+    pub from: u16,
+    pub to: Option<u16>,
+    pub name: String,
+    pub slugs: Vec<String>,
+}
+
+impl Lineage {
+    const VERSION: u32 = 1;
+
+    // Lineage dataset shipped with the crate
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_LINEAGE).expect("builtin lineage dataset is valid")
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let lineage: Self = toml::from_str(s).with_context(|| "parse lineage dataset")?;
+        if lineage.version != Self::VERSION {
+            return Err(anyhow::anyhow!(
+                "unsupported lineage dataset version: {} (expected {})",
+                lineage.version,
+                Self::VERSION
+            ));
+        }
+        lineage.validate()?;
+        Ok(lineage)
+    }
+
+    // A slug must resolve to a single constructor era in any season
+    fn validate(&self) -> Result<()> {
+        let mut eras = vec![];
+        for constructor in &self.constructors {
+            if eras
+                .iter()
+                .any(|(c, _): &(&Constructor, _)| c.id == constructor.id)
+            {
+                return Err(anyhow::anyhow!(
+                    "duplicate constructor `{}` in lineage dataset",
+                    constructor.id
+                ));
+            }
+            if constructor.eras.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "constructor `{}` has no era in lineage dataset",
+                    constructor.id
+                ));
+            }
+            for era in &constructor.eras {
+                if era.to.is_some_and(|to| to < era.from) || era.slugs.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "invalid era of constructor `{}` in lineage dataset: {}",
+                        constructor.id,
+                        era
+                    ));
+                }
+                eras.push((constructor, era));
+            }
+        }
+
+        for (idx, (constructor, era)) in eras.iter().enumerate() {
+            for (other_constructor, other) in &eras[idx + 1..] {
+                let slug = era.slugs.iter().find(|slug| {
+                    other
+                        .slugs
+                        .iter()
+                        .any(|other| is_prefix(slug, other) || is_prefix(other, slug))
+                });
+                if let Some(slug) = slug.filter(|_| era.overlaps(other)) {
+                    return Err(anyhow::anyhow!(
+                        "slug `{slug}` of constructor `{}` ({era}) is also used by `{}` ({other})",
+                        constructor.id,
+                        other_constructor.id
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Find the constructor, and the era, a season's team slug belongs to
+    pub fn resolve(&self, year: u16, team: &Team) -> Option<(&Constructor, &ConstructorEra)> {
+        self.resolve_slug(year, &team.name)
+    }
+
+    pub fn resolve_slug(&self, year: u16, slug: &str) -> Option<(&Constructor, &ConstructorEra)> {
+        let slug = slug.trim().to_lowercase();
+        self.constructors.iter().find_map(|constructor| {
+            constructor
+                .eras
+                .iter()
+                .find(|era| era.matches(year, &slug))
+                .map(|era| (constructor, era))
+        })
+    }
+
+    // Find a constructor by id, name or the name of one of its eras
+    pub fn find(&self, name: &str) -> Result<&Constructor> {
+        let mut resolver = Resolver::new("constructor");
+        for constructor in &self.constructors {
+            let mut keys = vec![constructor.name.as_str(), constructor.id.as_str()];
+            keys.extend(constructor.eras.iter().map(|era| era.name.as_str()));
+            resolver.add(constructor, &keys);
+        }
+        Ok(*resolver.resolve(name)?)
+    }
+
+    // Stable id to aggregate a team across seasons, falling back to the season's
+    // slug when the team is not part of the dataset
+    pub fn constructor_id(&self, year: u16, team: &Team) -> String {
+        match self.resolve(year, team) {
+            Some((constructor, _)) => constructor.id.clone(),
+            None => team.name.trim().to_lowercase(),
+        }
+    }
+}
+
+impl ConstructorEra {
+    pub fn contains(&self, year: u16) -> bool {
+        year >= self.from && self.to.is_none_or(|to| year <= to)
+    }
+
+    fn overlaps(&self, other: &ConstructorEra) -> bool {
+        self.to.is_none_or(|to| other.from <= to) && other.to.is_none_or(|to| self.from <= to)
+    }
+
+    fn matches(&self, year: u16, slug: &str) -> bool {
+        self.contains(year) && self.slugs.iter().any(|prefix| is_prefix(prefix, slug))
+    }
+}
+
+impl Constructor {
+    pub fn contains(&self, year: u16) -> bool {
+        self.eras.iter().any(|era| era.contains(year))
+    }
+}
+
+impl fmt::Display for ConstructorEra {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to {
+            Some(to) => write!(f, "{} {}-{}", self.name, self.from, to),
+            None => write!(f, "{} {}-", self.name, self.from),
+        }
+    }
+}
+
+// A slug matches a prefix when it is equal to it or starts with it followed by `_`
+fn is_prefix(prefix: &str, slug: &str) -> bool {
+    slug == prefix
+        || slug
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('_'))
+}
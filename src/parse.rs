@@ -1,8 +1,12 @@
 use scraper::element_ref::Select;
 use scraper::ElementRef;
+use scraper::Html;
 use scraper::Selector;
+use selectors::attr::CaseSensitivity;
+use serde::Serialize;
 
 use crate::prelude::*;
+use crate::schema::{PageSchema, Record};
 
 pub(crate) struct HtmlTable<'a> {
     inner: ElementRef<'a>,
@@ -34,12 +38,114 @@ impl<'a> HtmlTable<'a> {
         Ok(Self::new(inner))
     }
 
-    #[allow(unused)]
-    pub(crate) fn headers(&self) -> Select {
-        return self.inner.select(&self.s_header);
+    pub(crate) fn headers(&self) -> Select<'_, '_> {
+        self.inner.select(&self.s_header)
     }
 
-    pub(crate) fn rows(&self) -> Select {
-        return self.inner.select(&self.s_content);
+    // Lowercase header labels, skipping the layout-only `limiter` columns
+    pub(crate) fn header_names(&self) -> Vec<String> {
+        self.headers()
+            .filter(|th| {
+                !th.value()
+                    .has_class("limiter", CaseSensitivity::AsciiCaseInsensitive)
+            })
+            .map(|th| th.text().collect::<String>().trim().to_lowercase())
+            .collect()
+    }
+
+    pub(crate) fn rows(&self) -> Select<'_, '_> {
+        self.inner.select(&self.s_content)
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TableReport {
+    pub selector: String,
+    pub found: bool,
+    pub headers: Vec<String>,
+    pub missing_headers: Vec<String>,
+    pub rows: usize,
+    pub parsed_rows: usize,
+    // first few row errors
+    pub errors: Vec<String>,
+}
+
+impl TableReport {
+    const MAX_ERRORS: usize = 5;
+
+    // Check a page against a table layout without failing on the first bad row
+    pub(crate) fn check<T>(
+        html: &str,
+        schema: Result<&PageSchema>,
+        parse_entry: impl Fn(&Record) -> Result<T>,
+    ) -> Self {
+        let schema = match schema {
+            Ok(schema) => schema,
+            Err(err) => {
+                return Self {
+                    errors: vec![format!("{err:#}")],
+                    ..Default::default()
+                }
+            }
+        };
+
+        let document = Html::parse_document(html);
+        let document_root = document.root_element();
+
+        let mut report = Self {
+            selector: schema.table.clone(),
+            ..Default::default()
+        };
+
+        let table = match HtmlTable::parse(&document_root, &schema.table) {
+            Ok(table) => table,
+            Err(err) => {
+                report.errors.push(format!("{err:#}"));
+                return report;
+            }
+        };
+        report.found = true;
+
+        report.headers = table.header_names();
+        report.missing_headers = schema
+            .expected_headers()
+            .into_iter()
+            .filter(|expected| {
+                !expected
+                    .split('|')
+                    .any(|e| report.headers.iter().any(|h| h == e))
+            })
+            .collect();
+
+        let layout = schema.layout(&report.headers);
+        for (idx, row) in table.rows().enumerate() {
+            report.rows += 1;
+            let entry = match &layout {
+                Ok(layout) => layout.record(&row).and_then(|r| parse_entry(&r)),
+                Err(err) => Err(anyhow::anyhow!("{err:#}")),
+            };
+            match entry {
+                Ok(_) => report.parsed_rows += 1,
+                Err(err) if report.errors.len() < Self::MAX_ERRORS => {
+                    report.errors.push(format!("row {idx}: {err:#}"))
+                }
+                Err(_) => {}
+            }
+        }
+        report
+    }
+
+    pub fn parse_rate(&self) -> f32 {
+        match self.rows {
+            0 => 0.0,
+            rows => self.parsed_rows as f32 / rows as f32,
+        }
+    }
+
+    pub fn is_healthy(&self, min_parse_rate: f32) -> bool {
+        self.found
+            && self.missing_headers.is_empty()
+            && self.rows > 0
+            && self.parse_rate() >= min_parse_rate
     }
 }
@@ -0,0 +1,12 @@
+mod standings;
+mod system;
+
+pub use standings::Standings;
+pub use standings::StandingsEntry;
+
+pub use system::ConstructorScoring;
+pub use system::CountedResults;
+pub use system::FastestLapBonus;
+pub use system::PointsSystem;
+pub use system::FIRST_CONSTRUCTORS_SEASON;
+pub use system::FIRST_SPRINT_SEASON;
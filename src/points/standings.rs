@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::prelude::*;
+use crate::types::{FastestLapSummary, RaceResult};
+
+use super::{
+    ConstructorScoring, CountedResults, PointsSystem, FIRST_CONSTRUCTORS_SEASON,
+    FIRST_SPRINT_SEASON,
+};
+
+#[derive(Debug)]
+pub struct Standings {
+    pub year: u16,
+    pub system: PointsSystem,
+    pub drivers: Vec<StandingsEntry>,
+    pub constructors: Vec<StandingsEntry>,
+}
+
+#[derive(Default, Debug)]
+pub struct StandingsEntry {
+    pub pos: u32,
+    pub name: String,
+    pub pts: f32,
+    pub dropped_pts: f32,
+    pub wins: u32,
+    pub actual_pts: f32,
+}
+
+impl Standings {
+    // Re-rank drivers and constructors of a season under the given points system.
+    //
+    // `races` must be in calendar order for split season rules to apply correctly.
+    // Fastest lap bonuses are only awarded when `fastest_laps` is provided.
+    // Seasons with sprint races are refused, their results are not scraped, and
+    // constructors are only ranked from 1958 when their championship started.
+    pub fn simulate(
+        system: &PointsSystem,
+        races: &[RaceResult],
+        fastest_laps: Option<&FastestLapSummary>,
+    ) -> Result<Self> {
+        let year = races.first().map(|r| r.year).unwrap_or_default();
+        Self::check_season(year)?;
+
+        let mut drivers: HashMap<&str, Tally> = HashMap::new();
+        let mut constructors: HashMap<&str, Tally> = HashMap::new();
+
+        for (round, race) in races.iter().enumerate() {
+            let fastest_lap_driver = fastest_laps
+                .and_then(|fl| fl.for_round(round, &race.circuit.display_name))
+                .map(|fl| fl.driver.as_str());
+            debug!(
+                "[{}][{}] fastest lap: {:?}",
+                year, race.circuit.display_name, fastest_lap_driver
+            );
+
+            let factor = system.race_factor(race.year, &race.circuit);
+            let mut race_constructors: HashMap<&str, f32> = HashMap::new();
+            for entry in &race.data {
+                let pos = entry.position();
+                let mut pts = system.position_points(pos) * factor;
+                // no fastest lap bonus at half points races
+                if fastest_lap_driver == Some(entry.driver.as_str()) && factor >= 1.0 {
+                    pts += system.fastest_lap_points(pos);
+                }
+
+                let driver = drivers.entry(&entry.driver).or_default();
+                driver.record(round, pts, pos, entry.points());
+
+                // constructors only score fastest laps once every car counts
+                let constructor_pts = match system.constructors {
+                    ConstructorScoring::BestCarOnly => system.position_points(pos) * factor,
+                    ConstructorScoring::AllCars => pts,
+                };
+                let race_pts = race_constructors.entry(&entry.car).or_default();
+                *race_pts = match system.constructors {
+                    ConstructorScoring::BestCarOnly => race_pts.max(constructor_pts),
+                    ConstructorScoring::AllCars => *race_pts + constructor_pts,
+                };
+
+                let constructor = constructors.entry(&entry.car).or_default();
+                constructor.finish(pos);
+                constructor.actual_pts += entry.points();
+            }
+            for (car, pts) in race_constructors {
+                constructors
+                    .entry(car)
+                    .or_default()
+                    .scores
+                    .push((round, pts));
+            }
+        }
+
+        let constructors = match year >= FIRST_CONSTRUCTORS_SEASON {
+            true => rank(constructors, CountedResults::All),
+            false => vec![],
+        };
+        Ok(Self {
+            year,
+            system: system.clone(),
+            drivers: rank(drivers, system.counted),
+            constructors,
+        })
+    }
+
+    pub fn check_season(year: u16) -> Result<()> {
+        if year >= FIRST_SPRINT_SEASON {
+            return Err(anyhow::anyhow!(
+                "can't simulate season {year}: sprint results are not scraped"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+struct Tally {
+    scores: Vec<(usize, f32)>,
+    finishes: Vec<u32>,
+    actual_pts: f32,
+}
+
+impl Tally {
+    fn record(&mut self, round: usize, pts: f32, pos: Option<u32>, actual_pts: f32) {
+        self.scores.push((round, pts));
+        self.finish(pos);
+        self.actual_pts += actual_pts;
+    }
+
+    // count finishing positions, used to break ties on countback
+    fn finish(&mut self, pos: Option<u32>) {
+        if let Some(idx) = pos.and_then(|pos| pos.checked_sub(1)) {
+            let idx = idx as usize;
+            if self.finishes.len() <= idx {
+                self.finishes.resize(idx + 1, 0);
+            }
+            self.finishes[idx] += 1;
+        }
+    }
+
+    fn total(&self) -> f32 {
+        self.scores.iter().map(|(_, pts)| pts).sum()
+    }
+
+    fn counted(&self, counted: CountedResults) -> f32 {
+        let pts = |(_, pts): &(usize, f32)| *pts;
+        match counted {
+            CountedResults::All => self.total(),
+            CountedResults::Best(n) => best_of(self.scores.iter().map(pts), n),
+            CountedResults::Split {
+                races,
+                first,
+                second,
+            } => {
+                let (before, after): (Vec<_>, Vec<_>) =
+                    self.scores.iter().partition(|(round, _)| *round < races);
+                best_of(before.into_iter().map(pts), first)
+                    + best_of(after.into_iter().map(pts), second)
+            }
+        }
+    }
+}
+
+fn best_of(scores: impl Iterator<Item = f32>, n: usize) -> f32 {
+    let mut scores: Vec<_> = scores.collect();
+    scores.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    scores.iter().take(n).sum()
+}
+
+fn rank(tallies: HashMap<&str, Tally>, counted: CountedResults) -> Vec<StandingsEntry> {
+    let mut ranked: Vec<_> = tallies
+        .into_iter()
+        .map(|(name, tally)| {
+            let pts = tally.counted(counted);
+            (name, pts, tally)
+        })
+        .collect();
+
+    ranked.sort_by(|(a_name, a_pts, a), (b_name, b_pts, b)| {
+        b_pts
+            .partial_cmp(a_pts)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| countback(&a.finishes, &b.finishes))
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (name, pts, tally))| StandingsEntry {
+            pos: idx as u32 + 1,
+            name: name.to_string(),
+            pts,
+            dropped_pts: tally.total() - pts,
+            wins: tally.finishes.first().copied().unwrap_or_default(),
+            actual_pts: tally.actual_pts,
+        })
+        .collect()
+}
+
+// more wins first, then more second places, ...
+fn countback(a: &[u32], b: &[u32]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|idx| {
+            let a = a.get(idx).copied().unwrap_or_default();
+            let b = b.get(idx).copied().unwrap_or_default();
+            b.cmp(&a)
+        })
+        .find(|ord| ord.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
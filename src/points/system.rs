@@ -0,0 +1,185 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::prelude::*;
+use crate::season::FIRST_SEASON;
+use crate::types::Circuit;
+
+// First season with sprint races, their results are not scraped so the standings
+// of these seasons can't be simulated
+pub const FIRST_SPRINT_SEASON: u16 = 2021;
+
+// First season of the constructors' championship
+pub const FIRST_CONSTRUCTORS_SEASON: u16 = 1958;
+
+// Races stopped before enough distance was covered, which awarded half points.
+//
+// Belgium 2021 also did, but seasons with sprint races can't be simulated.
+const HALF_POINTS: [(u16, &str); 5] = [
+    (1975, "spain"),
+    (1975, "austria"),
+    (1984, "monaco"),
+    (1991, "australia"),
+    (2009, "malaysia"),
+];
+
+// The last race of 2014 awarded double points
+const DOUBLE_POINTS: [(u16, &str); 1] = [(2014, "abu-dhabi")];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FastestLapBonus {
+    // Awarded to the fastest lap setter regardless of the finishing position
+    Always(f32),
+    // Only awarded when the fastest lap setter finishes inside the given position
+    InTop(f32, u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountedResults {
+    All,
+    // Only the best `n` results of the season count
+    Best(usize),
+    // The season is split after `races` rounds, with the best `first` results of the
+    // first part and the best `second` results of the remaining rounds counting
+    Split {
+        races: usize,
+        first: usize,
+        second: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstructorScoring {
+    // Only the highest placed car of a constructor scores (1958-1978)
+    BestCarOnly,
+    AllCars,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointsSystem {
+    pub year: u16,
+    pub positions: Vec<f32>,
+    pub fastest_lap: Option<FastestLapBonus>,
+    pub counted: CountedResults,
+    pub constructors: ConstructorScoring,
+}
+
+impl PointsSystem {
+    // Scoring rules that were in use during the given season.
+    //
+    // Shared drives (1950-1957) and the constructors' own dropped results rules
+    // are not modelled: every counted driver result also counts for the car.
+    pub fn for_year(year: u16) -> Result<Self> {
+        if year < FIRST_SEASON {
+            return Err(anyhow::anyhow!(
+                "no points system for year `{year}`: first season is {FIRST_SEASON}"
+            ));
+        }
+
+        let positions = match year {
+            1950..=1959 => vec![8.0, 6.0, 4.0, 3.0, 2.0],
+            1960 => vec![8.0, 6.0, 4.0, 3.0, 2.0, 1.0],
+            1961..=1990 => vec![9.0, 6.0, 4.0, 3.0, 2.0, 1.0],
+            1991..=2002 => vec![10.0, 6.0, 4.0, 3.0, 2.0, 1.0],
+            2003..=2009 => vec![10.0, 8.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0],
+            _ => vec![25.0, 18.0, 15.0, 12.0, 10.0, 8.0, 6.0, 4.0, 2.0, 1.0],
+        };
+
+        let fastest_lap = match year {
+            1950..=1959 => Some(FastestLapBonus::Always(1.0)),
+            2019..=2024 => Some(FastestLapBonus::InTop(1.0, 10)),
+            _ => None,
+        };
+
+        let split = |races, first, second| CountedResults::Split {
+            races,
+            first,
+            second,
+        };
+        let counted = match year {
+            1950..=1953 => CountedResults::Best(4),
+            1954..=1957 => CountedResults::Best(5),
+            1958 => CountedResults::Best(6),
+            1959 => CountedResults::Best(5),
+            1960 => CountedResults::Best(6),
+            1961..=1962 => CountedResults::Best(5),
+            1963..=1965 => CountedResults::Best(6),
+            1966 => CountedResults::Best(5),
+            1967 => split(6, 5, 4),
+            1968 => split(6, 5, 5),
+            1969 => split(6, 5, 4),
+            1970 => split(7, 6, 5),
+            1971 => split(6, 5, 4),
+            1972 => split(6, 5, 5),
+            1973..=1974 => split(8, 7, 6),
+            1975 => split(7, 6, 6),
+            1976 => split(8, 7, 7),
+            1977 => split(9, 8, 7),
+            1978 => split(8, 7, 7),
+            1979 => split(7, 4, 4),
+            1980 => split(7, 5, 5),
+            1981..=1990 => CountedResults::Best(11),
+            _ => CountedResults::All,
+        };
+
+        let constructors = match year {
+            ..=1978 => ConstructorScoring::BestCarOnly,
+            _ => ConstructorScoring::AllCars,
+        };
+
+        Ok(Self {
+            year,
+            positions,
+            fastest_lap,
+            counted,
+            constructors,
+        })
+    }
+
+    pub fn position_points(&self, pos: Option<u32>) -> f32 {
+        pos.and_then(|pos| pos.checked_sub(1))
+            .and_then(|idx| self.positions.get(idx as usize))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Share of the points awarded at a race. Half points races are halved under
+    // every system, the double points of 2014 only apply under its own system
+    pub fn race_factor(&self, year: u16, circuit: &Circuit) -> f32 {
+        let race = (year, circuit.name.as_str());
+        if HALF_POINTS.contains(&race) {
+            0.5
+        } else if self.year == 2014 && DOUBLE_POINTS.contains(&race) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    pub fn fastest_lap_points(&self, pos: Option<u32>) -> f32 {
+        match self.fastest_lap {
+            Some(FastestLapBonus::Always(pts)) => pts,
+            Some(FastestLapBonus::InTop(pts, top)) if pos.is_some_and(|pos| pos <= top) => pts,
+            _ => 0.0,
+        }
+    }
+}
+
+impl FromStr for PointsSystem {
+    type Err = anyhow::Error;
+
+    // Systems are named after a season they were used in (e.g. `2010`)
+    fn from_str(s: &str) -> Result<Self> {
+        let year = s
+            .trim()
+            .parse::<u16>()
+            .with_context(|| format!("parse points system: expected a season, got `{s}`"))?;
+        Self::for_year(year)
+    }
+}
+
+impl fmt::Display for PointsSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.year)
+    }
+}
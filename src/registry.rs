@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::prelude::*;
+use crate::resolve::fold;
+use crate::types::{
+    DriverFragment, DriverName, DriverSummary, FastestLapSummary, QualifyingResult, RaceResult,
+    RaceSummary,
+};
+
+// Drivers of a season, keyed to resolve the names found in the other result pages
+#[derive(Default, Debug)]
+pub struct DriverRegistry {
+    pub year: u16,
+    drivers: Vec<(DriverFragment, DriverName)>,
+    by_name: HashMap<String, usize>,
+    by_code: HashMap<(String, String), usize>,
+    by_last: HashMap<String, Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionSource {
+    RaceWinner,
+    RaceResult,
+    QualifyingResult,
+    FastestLap,
+}
+
+// A driver name which could not be matched to the season's drivers
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedMention {
+    pub source: MentionSource,
+    pub grand_prix: String,
+    pub name: DriverName,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct RegistryReport {
+    pub year: u16,
+    pub mentions: usize,
+    pub resolved: usize,
+    pub unresolved: Vec<UnresolvedMention>,
+}
+
+impl DriverRegistry {
+    // The driver summary is the only page carrying the stable driver ids
+    pub fn from_summary(summary: &DriverSummary) -> Result<Self> {
+        let mut registry = Self {
+            year: summary.year,
+            ..Default::default()
+        };
+        for entry in &summary.data {
+            let fragment = entry.driver().with_context(|| {
+                format!(
+                    "obtain driver infos from summary data (driver: `{}`)",
+                    entry.driver
+                )
+            })?;
+            registry.add(fragment, entry.driver_name.clone());
+        }
+        Ok(registry)
+    }
+
+    pub fn drivers(&self) -> impl Iterator<Item = &DriverFragment> {
+        self.drivers.iter().map(|(fragment, _)| fragment)
+    }
+
+    // Match on the full name first, then on the code and last name, and at last
+    // on the last name when no other driver of the season shares it
+    pub fn resolve(&self, name: &DriverName) -> Option<&DriverFragment> {
+        let idx = self
+            .by_name
+            .get(&fold(&name.full_name()))
+            .or_else(|| {
+                let code = name.code.as_ref()?;
+                self.by_code.get(&(code.to_uppercase(), fold(&name.last)))
+            })
+            .or_else(|| match self.by_last.get(&fold(&name.last))?.as_slice() {
+                [idx] => Some(idx),
+                _ => None,
+            })?;
+        Some(&self.drivers[*idx].0)
+    }
+
+    // Resolve every driver mentioned in the season's result pages
+    pub fn audit(
+        &self,
+        race_summary: Option<&RaceSummary>,
+        races: &[RaceResult],
+        qualifying: &[QualifyingResult],
+        fastest_laps: Option<&FastestLapSummary>,
+    ) -> RegistryReport {
+        let mut report = RegistryReport {
+            year: self.year,
+            ..Default::default()
+        };
+
+        for entry in race_summary.iter().flat_map(|s| &s.data) {
+            report.record(
+                self,
+                MentionSource::RaceWinner,
+                &entry.grand_prix,
+                &entry.winner_name,
+            );
+        }
+        for race in races {
+            for entry in &race.data {
+                report.record(
+                    self,
+                    MentionSource::RaceResult,
+                    &race.circuit.display_name,
+                    &entry.driver_name,
+                );
+            }
+        }
+        for session in qualifying {
+            for entry in &session.data {
+                report.record(
+                    self,
+                    MentionSource::QualifyingResult,
+                    &session.circuit.display_name,
+                    &entry.driver_name,
+                );
+            }
+        }
+        for entry in fastest_laps.iter().flat_map(|s| &s.data) {
+            report.record(
+                self,
+                MentionSource::FastestLap,
+                &entry.grand_prix,
+                &entry.driver_name,
+            );
+        }
+        report
+    }
+
+    fn add(&mut self, fragment: DriverFragment, name: DriverName) {
+        let idx = self.drivers.len();
+        self.by_name.entry(fold(&name.full_name())).or_insert(idx);
+        if let Some(code) = &name.code {
+            self.by_code
+                .entry((code.to_uppercase(), fold(&name.last)))
+                .or_insert(idx);
+        }
+        self.by_last.entry(fold(&name.last)).or_default().push(idx);
+        self.drivers.push((fragment, name));
+    }
+}
+
+impl RegistryReport {
+    fn record(
+        &mut self,
+        registry: &DriverRegistry,
+        source: MentionSource,
+        grand_prix: &str,
+        name: &DriverName,
+    ) {
+        self.mentions += 1;
+        match registry.resolve(name) {
+            Some(_) => self.resolved += 1,
+            None => self.unresolved.push(UnresolvedMention {
+                source,
+                grand_prix: grand_prix.to_string(),
+                name: name.clone(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// Find an entity (circuit, driver, team, ...) from a user provided name.
+//
+// Every entity is registered with its keys (slug, display name, code, ...),
+// which are compared case and accent insensitively, in order:
+//   - exact match of a key
+//   - a key, or one of its words, starting with the name
+//   - closest key by edit distance (transpositions included), tolerating a typo
+//     every four letters
+//
+// Names containing `*` or `?` are glob patterns, matched against the whole keys.
+#[derive(Debug)]
+pub struct Resolver<T> {
+    kind: String,
+    entries: Vec<(T, String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveError {
+    pub kind: String,
+    pub query: String,
+    // candidates when the name was ambiguous, or the closest keys otherwise
+    pub suggestions: Vec<String>,
+    pub ambiguous: bool,
+}
+
+impl<T> Resolver<T> {
+    const MAX_SUGGESTIONS: usize = 3;
+    // shorter names only match a key exactly
+    const MIN_PARTIAL_LEN: usize = 3;
+
+    pub fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            entries: vec![],
+        }
+    }
+
+    // The first key is the name suggested when no entity matches
+    pub fn add<S: AsRef<str>>(&mut self, item: T, keys: &[S]) {
+        let name = keys
+            .first()
+            .map(|key| key.as_ref().trim().to_string())
+            .unwrap_or_default();
+        let keys = keys
+            .iter()
+            .map(|key| fold(key.as_ref()))
+            .filter(|key| !key.is_empty())
+            .collect();
+        self.entries.push((item, name, keys));
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().map(|(item, _, _)| item)
+    }
+
+    pub fn resolve(&self, query: &str) -> Result<&T, ResolveError> {
+        let idx = self.find(query)?;
+        Ok(&self.entries[idx].0)
+    }
+
+    // Every entity matching one of the names or patterns, with its position in
+    // registration order; all the entities when no name is given
+    pub fn select<S: AsRef<str>>(&self, queries: &[S]) -> Result<Vec<(usize, &T)>, ResolveError> {
+        let mut selected = vec![queries.is_empty(); self.entries.len()];
+        for query in queries {
+            let query = query.as_ref();
+            if is_pattern(query) {
+                let matches = self.matching_pattern(query);
+                if matches.is_empty() {
+                    return Err(ResolveError {
+                        kind: self.kind.clone(),
+                        query: query.trim().to_string(),
+                        suggestions: vec![],
+                        ambiguous: false,
+                    });
+                }
+                for idx in matches {
+                    selected[idx] = true;
+                }
+            } else {
+                selected[self.find(query)?] = true;
+            }
+        }
+        Ok(self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| selected[*idx])
+            .map(|(idx, (item, _, _))| (idx, item))
+            .collect())
+    }
+
+    fn find(&self, query: &str) -> Result<usize, ResolveError> {
+        let folded = fold(query);
+        let error = |suggestions: Vec<String>, ambiguous: bool| ResolveError {
+            kind: self.kind.clone(),
+            query: query.trim().to_string(),
+            suggestions,
+            ambiguous,
+        };
+        if folded.is_empty() {
+            return Err(error(vec![], false));
+        }
+
+        let exact = self.matching(|key| *key == folded);
+        let prefix = self.matching(|key| starts_with(key, &folded));
+        for matches in [exact, prefix] {
+            match matches.as_slice() {
+                [] => continue,
+                [idx] => return Ok(*idx),
+                _ => {
+                    let suggestions = matches.iter().map(|idx| self.display(*idx)).collect();
+                    return Err(error(suggestions, true));
+                }
+            }
+        }
+
+        // one typo every four letters
+        let max_distance = (folded.chars().count() / 4).max(1);
+        let ranked = self.ranked(&folded);
+        match ranked.as_slice() {
+            [(distance, idx), rest @ ..]
+                if *distance <= max_distance && rest.first().is_none_or(|(d, _)| d > distance) =>
+            {
+                Ok(*idx)
+            }
+            _ => {
+                let suggestions = ranked
+                    .iter()
+                    .take(Self::MAX_SUGGESTIONS)
+                    .map(|(_, idx)| self.display(*idx))
+                    .collect();
+                Err(error(suggestions, false))
+            }
+        }
+    }
+
+    // Every entity matching the name, with the first rule any entity matches
+    // (see `Resolver`), e.g. the races of a season held at the same circuit
+    pub fn matches(&self, query: &str) -> Vec<&T> {
+        let folded = fold(query);
+        let partial = folded.chars().count() >= Self::MIN_PARTIAL_LEN;
+        let mut matches = self.matching(|key| *key == folded);
+        if matches.is_empty() && partial {
+            matches = self.matching(|key| starts_with(key, &folded));
+        }
+        if matches.is_empty() && partial {
+            let max_distance = (folded.chars().count() / 4).max(1);
+            let ranked = self.ranked(&folded);
+            let closest = ranked.first().map(|(distance, _)| *distance);
+            matches = ranked
+                .iter()
+                .filter(|(distance, _)| Some(*distance) == closest && *distance <= max_distance)
+                .map(|(_, idx)| *idx)
+                .collect();
+            matches.sort();
+        }
+        matches
+            .into_iter()
+            .map(|idx| &self.entries[idx].0)
+            .collect()
+    }
+
+    fn matching(&self, matches: impl Fn(&String) -> bool) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, keys))| keys.iter().any(&matches))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    fn matching_pattern(&self, pattern: &str) -> Vec<usize> {
+        // fold the literal parts only, keeping the wildcards
+        let mut folded = String::new();
+        let mut literal = String::new();
+        for c in pattern.chars() {
+            if c == '*' || c == '?' {
+                folded.push_str(&fold(&literal));
+                folded.push(c);
+                literal.clear();
+            } else {
+                literal.push(c);
+            }
+        }
+        folded.push_str(&fold(&literal));
+
+        let pattern: Vec<char> = folded.chars().collect();
+        self.matching(|key| glob_match(&pattern, &key.chars().collect::<Vec<_>>()))
+    }
+
+    // Entries by the edit distance of their closest key
+    fn ranked(&self, query: &str) -> Vec<(usize, usize)> {
+        let mut ranked: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (_, _, keys))| {
+                let distance = keys
+                    .iter()
+                    .map(|key| strsim::osa_distance(key, query))
+                    .min()?;
+                Some((distance, idx))
+            })
+            .collect();
+        ranked.sort();
+        ranked
+    }
+
+    fn display(&self, idx: usize) -> String {
+        self.entries[idx].1.clone()
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ambiguous {
+            true => write!(f, "ambiguous {} name: `{}`", self.kind, self.query)?,
+            false => write!(f, "find {} with name: `{}`", self.kind, self.query)?,
+        }
+        match (self.ambiguous, self.suggestions.as_slice()) {
+            (_, []) => Ok(()),
+            (true, suggestions) => write!(f, ", matches: {}", suggestions.join(", ")),
+            (false, suggestions) => write!(f, ", did you mean: {}?", suggestions.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+// A key, or one of its words, starting with the folded name
+fn starts_with(key: &str, folded: &str) -> bool {
+    key.starts_with(folded) || key.split(' ').any(|word| word.starts_with(folded))
+}
+
+fn is_pattern(query: &str) -> bool {
+    query.contains(['*', '?'])
+}
+
+// `*` matches any sequence of characters and `?` a single one
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, _) => text.is_empty(),
+        (Some('*'), _) => {
+            glob_match(&pattern[1..], text) || (!text.is_empty() && glob_match(pattern, &text[1..]))
+        }
+        (Some(_), None) => false,
+        (Some('?'), Some(_)) => glob_match(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) => p == t && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+// Case and accent insensitive form of a name, separators turned into spaces
+// Example: `Kimi-Räikkönen` -> `kimi raikkonen`
+pub(crate) fn fold(s: &str) -> String {
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// Identifier derived from a name, shared by the entities exposed by id
+// Example: `Kimi Räikkönen` -> `kimi_raikkonen`
+pub fn slug(s: &str) -> String {
+    fold(s).replace(' ', "_")
+}
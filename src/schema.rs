@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use scraper::{ElementRef, Selector};
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::types::{DriverName, ScrapperHelper};
+
+const BUILTIN_SCHEMAS: &str = include_str!("../data/schemas.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Schemas {
+    pub version: u32,
+    pub pages: Vec<PageSchema>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PageSchema {
+    pub kind: PageKind,
+    pub from: Option<u16>,
+    pub to: Option<u16>,
+    pub table: String,
+    // block with the page's own details, e.g. the venue and dates of a race
+    pub header: Option<String>,
+    pub columns: Vec<ColumnSchema>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageKind {
+    RaceSummary,
+    RaceResult,
+    QualifyingResult,
+    DriverSummary,
+    DriverResult,
+    TeamSummary,
+    TeamResult,
+    FastestLapSummary,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ColumnSchema {
+    pub field: String,
+    pub header: Headers,
+    #[serde(default)]
+    pub extract: Extract,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Headers {
+    One(String),
+    // the last non-empty cell among the columns present in the table
+    Any(Vec<String>),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Extract {
+    #[default]
+    Text,
+    InnerHtml,
+    JoinSpans,
+    Link,
+    Href,
+    // first name, last name and code spans of a driver cell
+    DriverName,
+}
+
+impl Schemas {
+    const VERSION: u32 = 1;
+
+    // Schemas shipped with the crate
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_SCHEMAS).expect("builtin page schemas are valid")
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let schemas: Self = toml::from_str(s).with_context(|| "parse page schemas")?;
+        if schemas.version != Self::VERSION {
+            return Err(anyhow::anyhow!(
+                "unsupported page schemas version: {} (expected {})",
+                schemas.version,
+                Self::VERSION
+            ));
+        }
+        for page in &schemas.pages {
+            page.validate()
+                .with_context(|| format!("page schema: {}", page.kind))?;
+        }
+        Ok(schemas)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let s = std::fs::read_to_string(path)
+            .with_context(|| format!("read page schemas: {}", path.display()))?;
+        Self::parse(&s).with_context(|| format!("load page schemas: {}", path.display()))
+    }
+
+    // Layouts of `other` take precedence over the current ones
+    pub fn extend(mut self, other: Self) -> Self {
+        let mut pages = other.pages;
+        pages.append(&mut self.pages);
+        self.pages = pages;
+        self
+    }
+
+    pub fn page(&self, kind: PageKind, year: u16) -> Result<&PageSchema> {
+        self.pages
+            .iter()
+            .find(|page| page.kind == kind && page.contains(year))
+            .with_context(|| format!("no page schema for {kind} in {year}"))
+    }
+}
+
+impl Default for Schemas {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PageSchema {
+    pub fn contains(&self, year: u16) -> bool {
+        self.from.is_none_or(|from| year >= from) && self.to.is_none_or(|to| year <= to)
+    }
+
+    // Header labels the table must have for every column to be found
+    pub fn expected_headers(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| match &column.header {
+                Headers::One(header) => header.clone(),
+                Headers::Any(headers) => headers.join("|"),
+            })
+            .collect()
+    }
+
+    // Locate the columns in a table from its header labels
+    pub(crate) fn layout(&self, headers: &[String]) -> Result<Layout<'_>> {
+        let mut columns = vec![];
+        for column in &self.columns {
+            let indices: Vec<usize> = match &column.header {
+                Headers::One(header) => headers
+                    .iter()
+                    .position(|h| h == header)
+                    .into_iter()
+                    .collect(),
+                Headers::Any(labels) => headers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, h)| labels.contains(h))
+                    .map(|(idx, _)| idx)
+                    .collect(),
+            };
+            if indices.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "no column for field `{}` (headers: {:?})",
+                    column.field,
+                    headers
+                ));
+            }
+            columns.push((column, indices));
+        }
+        Ok(Layout {
+            header_count: headers.len(),
+            columns,
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if Selector::parse(&self.table).is_err() {
+            return Err(anyhow::anyhow!("invalid table selector: `{}`", self.table));
+        }
+        if let Some(header) = &self.header {
+            if Selector::parse(header).is_err() {
+                return Err(anyhow::anyhow!("invalid header selector: `{header}`"));
+            }
+        }
+        for column in &self.columns {
+            if let Headers::Any(headers) = &column.header {
+                if headers.is_empty() {
+                    return Err(anyhow::anyhow!("no header for field `{}`", column.field));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PageKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PageKind::RaceSummary => "race_summary",
+            PageKind::RaceResult => "race_result",
+            PageKind::QualifyingResult => "qualifying_result",
+            PageKind::DriverSummary => "driver_summary",
+            PageKind::DriverResult => "driver_result",
+            PageKind::TeamSummary => "team_summary",
+            PageKind::TeamResult => "team_result",
+            PageKind::FastestLapSummary => "fastest_lap_summary",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl Extract {
+    fn apply(&self, helper: &ScrapperHelper, cell: &ElementRef) -> Result<String> {
+        match self {
+            Extract::Text => helper.text(cell),
+            Extract::InnerHtml => helper.inner_html(cell),
+            Extract::JoinSpans => helper.join_spans(cell),
+            Extract::Link => helper.link(cell),
+            Extract::Href => helper.href(&helper.link_elem(cell)?),
+            Extract::DriverName => Ok(helper.driver_name(cell)?.full_name()),
+        }
+    }
+}
+
+// Columns of a table resolved against a page schema
+pub(crate) struct Layout<'a> {
+    header_count: usize,
+    columns: Vec<(&'a ColumnSchema, Vec<usize>)>,
+}
+
+impl Layout<'_> {
+    pub(crate) fn record(&self, row: &ElementRef) -> Result<Record> {
+        let helper = ScrapperHelper::new();
+
+        let cols: Vec<_> = helper.table_cols(row).collect();
+        if cols.len() != self.header_count {
+            return Err(anyhow::anyhow!("invalid column count"));
+        }
+
+        let mut record = Record::default();
+        for (column, indices) in &self.columns {
+            if column.extract == Extract::DriverName {
+                let name = helper
+                    .driver_name(&cols[indices[0]])
+                    .with_context(|| format!("column: {}", column.field))?;
+                record.fields.insert(column.field.clone(), name.full_name());
+                record.driver_names.insert(column.field.clone(), name);
+                continue;
+            }
+
+            let mut value = String::new();
+            for idx in indices {
+                let cell = column
+                    .extract
+                    .apply(&helper, &cols[*idx])
+                    .with_context(|| format!("column: {}", column.field))?;
+                if !cell.is_empty() {
+                    value = cell;
+                }
+            }
+            record.fields.insert(column.field.clone(), value);
+        }
+        Ok(record)
+    }
+}
+
+// Cell values of a table row by field name
+#[derive(Debug, Default)]
+pub(crate) struct Record {
+    fields: HashMap<String, String>,
+    driver_names: HashMap<String, DriverName>,
+}
+
+impl Record {
+    pub(crate) fn get(&self, field: &str) -> Result<String> {
+        self.fields
+            .get(field)
+            .cloned()
+            .with_context(|| format!("no `{field}` column in page schema"))
+    }
+
+    pub(crate) fn driver_name(&self, field: &str) -> Result<DriverName> {
+        self.driver_names
+            .get(field)
+            .cloned()
+            .with_context(|| format!("no `{field}` driver name column in page schema"))
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::prelude::*;
+
+#[derive(Debug)]
+pub enum ResponseCache {
+    // Pages by url, with the time they were stored
+    Memory(Mutex<HashMap<String, (SystemTime, String)>>),
+    // One file per page, mirroring the url path under the directory
+    Disk(PathBuf),
+}
+
+impl ResponseCache {
+    pub fn in_memory() -> Self {
+        Self::Memory(Mutex::new(HashMap::new()))
+    }
+
+    pub fn on_disk<P: AsRef<Path>>(dir: P) -> Self {
+        Self::Disk(dir.as_ref().to_path_buf())
+    }
+
+    pub fn get(&self, url: &reqwest::Url) -> Result<Option<String>> {
+        Ok(self.lookup(url)?.map(|(_, body)| body))
+    }
+
+    // The cached page, unless it was stored `max_age` ago or earlier
+    pub fn get_within(&self, url: &reqwest::Url, max_age: Duration) -> Result<Option<String>> {
+        let page = self.lookup(url)?.filter(|(stored_at, _)| {
+            // a clock gone backwards keeps the page
+            stored_at.elapsed().map_or(true, |age| age < max_age)
+        });
+        Ok(page.map(|(_, body)| body))
+    }
+
+    fn lookup(&self, url: &reqwest::Url) -> Result<Option<(SystemTime, String)>> {
+        match self {
+            Self::Memory(pages) => {
+                let pages = pages.lock().expect("response cache lock poisoned");
+                Ok(pages.get(url.as_str()).cloned())
+            }
+            Self::Disk(dir) => {
+                let path = Self::page_path(dir, url);
+                if !path.exists() {
+                    return Ok(None);
+                }
+                let body = fs::read_to_string(&path)
+                    .with_context(|| format!("read cached page: {}", path.display()))?;
+                let stored_at = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .with_context(|| format!("read cached page time: {}", path.display()))?;
+                Ok(Some((stored_at, body)))
+            }
+        }
+    }
+
+    pub fn put(&self, url: &reqwest::Url, body: &str) -> Result<()> {
+        match self {
+            Self::Memory(pages) => {
+                let mut pages = pages.lock().expect("response cache lock poisoned");
+                pages.insert(url.to_string(), (SystemTime::now(), body.to_string()));
+            }
+            Self::Disk(dir) => {
+                let path = Self::page_path(dir, url);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("create cache dir: {}", parent.display()))?;
+                }
+                fs::write(&path, body)
+                    .with_context(|| format!("write cached page: {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn page_path(dir: &Path, url: &reqwest::Url) -> PathBuf {
+        // pages are stored as `index.html` since a url can be both a page and the
+        // prefix of other pages
+        // Example:
+        //   https://www.formula1.com/en/results.html/2023/races.html
+        //   -> {dir}/www.formula1.com/en/results.html/2023/races.html/index.html
+        let mut path = dir.join(url.host_str().unwrap_or("unknown-host"));
+        for segment in url.path_segments().into_iter().flatten() {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                continue;
+            }
+            path.push(segment);
+        }
+        match url.query() {
+            Some(query) => path.join(format!("index.html?{query}")),
+            None => path.join("index.html"),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::prelude::*;
+use crate::scrape::Scraper;
+
+// HTTP client settings of a `Scraper`
+//
+// Example:
+//   let scraper = ScraperConfig::new()
+//       .with_user_agent("my-team-stats/1.0 (ops@example.com)")
+//       .with_proxy("http://proxy.corp:3128")
+//       .with_timeout(Duration::from_secs(20))
+//       .build()?;
+#[derive(Debug, Clone)]
+pub struct ScraperConfig {
+    user_agent: String,
+    connect_timeout: Option<Duration>,
+    // applies to the whole request, reading the response included
+    timeout: Option<Duration>,
+    proxy: Option<String>,
+    root_certificates: Vec<PathBuf>,
+    compression: bool,
+    headers: Vec<(String, String)>,
+}
+
+impl Default for ScraperConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: Self::DEFAULT_USER_AGENT.to_string(),
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            root_certificates: vec![],
+            compression: true,
+            headers: vec![],
+        }
+    }
+}
+
+impl ScraperConfig {
+    pub const DEFAULT_USER_AGENT: &str = concat!("f1scraper/", env!("CARGO_PKG_VERSION"));
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    // Defaults to 30 seconds
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    // Proxy used for every request, instead of the `HTTP(S)_PROXY` environment
+    // variables
+    pub fn with_proxy<S: Into<String>>(mut self, url: S) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    // Trust an additional root certificate, PEM or DER encoded
+    pub fn with_root_certificate<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.root_certificates.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    // gzip, brotli and deflate encoded responses, enabled by default
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    // Header sent with every request
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn client(&self) -> Result<Client> {
+        let mut builder = ClientBuilder::new()
+            .user_agent(&self.user_agent)
+            .gzip(self.compression)
+            .brotli(self.compression)
+            .deflate(self.compression)
+            .default_headers(self.header_map()?);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(url) = &self.proxy {
+            let proxy =
+                reqwest::Proxy::all(url).with_context(|| format!("parse proxy url: {url}"))?;
+            builder = builder.proxy(proxy);
+        }
+        for path in &self.root_certificates {
+            builder = builder.add_root_certificate(Self::certificate(path)?);
+        }
+        builder.build().context("build http client")
+    }
+
+    pub fn build(&self) -> Result<Scraper> {
+        Ok(Scraper::new(self.client()?))
+    }
+
+    fn header_map(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.trim().as_bytes())
+                .with_context(|| format!("parse header name: `{name}`"))?;
+            let value = HeaderValue::from_str(value.trim())
+                .with_context(|| format!("parse value of header `{name}`"))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    fn certificate(path: &Path) -> Result<reqwest::Certificate> {
+        let bytes =
+            fs::read(path).with_context(|| format!("read root certificate: {}", path.display()))?;
+        reqwest::Certificate::from_pem(&bytes)
+            .or_else(|_| reqwest::Certificate::from_der(&bytes))
+            .with_context(|| format!("parse root certificate: {}", path.display()))
+    }
+}
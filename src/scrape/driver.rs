@@ -1,27 +1,37 @@
 use crate::prelude::*;
+use crate::schema::Schemas;
 use crate::scrape::ScrapeTarget;
-use crate::types::DriverFragment;
+use crate::types::{DriverFragment, DriverResult, DriverSummary};
 
 pub struct DriverResultSummaryTarget {
     url: reqwest::Url,
+    year: u16,
 }
 
 impl DriverResultSummaryTarget {
     pub fn new(year: u16) -> Result<Self> {
         let url = format!("https://www.formula1.com/en/results.html/{year}/drivers.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self { url, year })
     }
 }
 
 impl ScrapeTarget for DriverResultSummaryTarget {
+    type Output = DriverSummary;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<DriverSummary> {
+        DriverSummary::parse_with(html, self.year, schemas)
+    }
 }
 
 pub struct DriverResultTarget {
     url: reqwest::Url,
+    year: u16,
+    fragment: DriverFragment,
 }
 
 impl DriverResultTarget {
@@ -30,12 +40,22 @@ impl DriverResultTarget {
         let fragment_name = &fragment.name;
         let url = format!("https://www.formula1.com/en/results.html/{year}/drivers/{fragment_id}/{fragment_name}.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self {
+            url,
+            year,
+            fragment: fragment.clone(),
+        })
     }
 }
 
 impl ScrapeTarget for DriverResultTarget {
+    type Output = DriverResult;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<DriverResult> {
+        DriverResult::parse_with(html, self.year, &self.fragment, schemas)
+    }
 }
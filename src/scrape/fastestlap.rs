@@ -1,20 +1,29 @@
 use crate::prelude::*;
+use crate::schema::Schemas;
 use crate::scrape::ScrapeTarget;
+use crate::types::FastestLapSummary;
 
 pub struct FastestLapResultSummaryTarget {
     url: reqwest::Url,
+    year: u16,
 }
 
 impl FastestLapResultSummaryTarget {
     pub fn new(year: u16) -> Result<Self> {
         let url = format!("https://www.formula1.com/en/results.html/{year}/fastest-laps.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self { url, year })
     }
 }
 
 impl ScrapeTarget for FastestLapResultSummaryTarget {
+    type Output = FastestLapSummary;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<FastestLapSummary> {
+        FastestLapSummary::parse_with(html, self.year, schemas)
+    }
 }
@@ -1,46 +1,232 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
 use log::debug;
 use log::info;
+use log::warn;
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::schema::Schemas;
+use stats::Counters;
 
+mod cache;
+mod config;
 mod driver;
 mod fastestlap;
 mod race;
+mod results;
+mod season;
+mod stats;
 mod team;
 
+pub use cache::ResponseCache;
+pub use config::ScraperConfig;
+
 pub use driver::DriverResultSummaryTarget;
 pub use driver::DriverResultTarget;
 pub use fastestlap::FastestLapResultSummaryTarget;
+pub use race::QualifyingResultTarget;
 pub use race::RaceResultSummaryTarget;
 pub use race::RaceResultTarget;
+pub use results::PageEvent;
+pub use results::RaceResults;
+pub use results::ResultsError;
+pub use season::SeasonListTarget;
+pub use stats::ScrapeStats;
 pub use team::TeamResultSummaryTarget;
 pub use team::TeamResultTarget;
 
+// A page of the website, and how to parse it
+//
+// Implement it to scrape other pages with a `Scraper`, sharing its cache,
+// retries and statistics
 pub trait ScrapeTarget {
+    type Output;
+
     fn request(&self) -> reqwest::blocking::Request;
+
+    // Parse the scraped page, the schemas being the ones of the `Scraper`
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<Self::Output>;
+}
+
+// Step at which a scrape target failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Fetch,
+    Parse,
+}
+
+// Context attached to the errors of a scrape target, so callers can tell which
+// page failed and how
+// Example: `err.downcast_ref::<TargetFailure>()`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TargetFailure {
+    pub stage: Stage,
+    pub url: String,
 }
 
 #[derive(Debug, Default)]
 pub struct Scraper {
     client: reqwest::blocking::Client,
+    cache: Option<ResponseCache>,
+    schemas: Schemas,
+    retries: u32,
+    // minimum delay between two requests
+    interval: Option<Duration>,
+    last_request: Mutex<Option<Instant>>,
+    counters: Counters,
 }
 
 impl Scraper {
+    // Delay before the first retry, doubled on each further retry
+    const RETRY_DELAY: Duration = Duration::from_secs(1);
+
     pub fn new<C: Into<reqwest::blocking::Client>>(client: C) -> Self {
         Self {
             client: client.into(),
+            cache: None,
+            schemas: Schemas::builtin(),
+            retries: 0,
+            interval: None,
+            last_request: Mutex::new(None),
+            counters: Counters::default(),
         }
     }
 
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_schemas(mut self, schemas: Schemas) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    // Retry requests failing to connect, timing out or answered with a server
+    // error (or `429 Too Many Requests`) up to `retries` times
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    // Space the requests out to at most `requests_per_second`, cache hits aside
+    pub fn with_rate_limit(mut self, requests_per_second: f32) -> Self {
+        self.interval =
+            (requests_per_second > 0.0).then(|| Duration::from_secs_f32(1.0 / requests_per_second));
+        self
+    }
+
+    // Page layouts used to parse the scraped pages
+    pub fn schemas(&self) -> &Schemas {
+        &self.schemas
+    }
+
+    pub fn stats(&self) -> ScrapeStats {
+        self.counters.snapshot()
+    }
+
+    // Scrape a page and parse it, errors carrying a `TargetFailure`
+    pub fn fetch<T: ScrapeTarget>(&self, target: T) -> Result<T::Output> {
+        let html = self.scrape_request(target.request(), None)?;
+        self.parse(&target, &html)
+    }
+
+    // Fetch, reusing the cached page only when it was stored less than `max_age`
+    // ago
+    pub fn fetch_within<T: ScrapeTarget>(&self, target: T, max_age: Duration) -> Result<T::Output> {
+        let html = self.scrape_request(target.request(), Some(max_age))?;
+        self.parse(&target, &html)
+    }
+
+    // Fetch without looking up the cache, the cached page is replaced by the
+    // response
+    pub fn fetch_fresh<T: ScrapeTarget>(&self, target: T) -> Result<T::Output> {
+        let req = target.request();
+        let failure = TargetFailure::new(Stage::Fetch, req.url());
+        let html = self.execute(req).context(failure)?;
+        self.parse(&target, &html)
+    }
+
+    // Scrape a page without parsing it
     pub fn scrape(&self, target: impl ScrapeTarget) -> Result<String> {
+        self.scrape_request(target.request(), None)
+    }
+
+    // Scrape a page without looking up the cache nor parsing it, the cached page
+    // is replaced by the response
+    pub fn scrape_fresh(&self, target: impl ScrapeTarget) -> Result<String> {
         let req = target.request();
+        let failure = TargetFailure::new(Stage::Fetch, req.url());
+        self.execute(req).context(failure)
+    }
+
+    fn scrape_request(
+        &self,
+        req: reqwest::blocking::Request,
+        max_age: Option<Duration>,
+    ) -> Result<String> {
+        let failure = TargetFailure::new(Stage::Fetch, req.url());
+
+        if let Some(cache) = &self.cache {
+            let cached = match max_age {
+                Some(max_age) => cache.get_within(req.url(), max_age),
+                None => cache.get(req.url()),
+            };
+            if let Some(text) = cached.context(failure.clone())? {
+                debug!("[{}] Cache hit", req.url());
+                Counters::add(&self.counters.cache_hits, 1);
+                return Ok(text);
+            }
+        }
+        self.execute(req).context(failure)
+    }
+
+    fn parse<T: ScrapeTarget>(&self, target: &T, html: &str) -> Result<T::Output> {
+        let failure = TargetFailure::new(Stage::Parse, target.request().url());
+        target
+            .parse(html, &self.schemas)
+            .context(failure)
+            .inspect_err(|_| Counters::add(&self.counters.parse_failures, 1))
+    }
+
+    fn execute(&self, req: reqwest::blocking::Request) -> Result<String> {
         let url = &req.url().clone();
 
-        info!("[{}] Executing reqwest", url);
-        let response = self
-            .client
-            .execute(req)
-            .with_context(|| format!("execute scrape request: {url}"))?;
+        let mut retry = 0;
+        let response = loop {
+            let attempt = req
+                .try_clone()
+                .with_context(|| format!("clone scrape request: {url}"))?;
+            self.wait_turn();
+            info!("[{}] Executing reqwest", url);
+            let response = self.client.execute(attempt);
+
+            let transient = match &response {
+                Ok(response) => {
+                    let status = response.status();
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !transient || retry >= self.retries {
+                break response.with_context(|| format!("execute scrape request: {url}"))?;
+            }
+
+            let delay = Self::RETRY_DELAY * 2u32.pow(retry);
+            retry += 1;
+            Counters::add(&self.counters.retries, 1);
+            warn!(
+                "[{}] Transient failure, retry {} in {:?}",
+                url, retry, delay
+            );
+            thread::sleep(delay);
+        };
 
         info!(
             "[{}] Response: {:?} {}",
@@ -63,6 +249,48 @@ impl Scraper {
         let text = response
             .text()
             .with_context(|| format!("parse scrape response as text: {url}"))?;
+        Counters::add(&self.counters.pages_fetched, 1);
+        Counters::add(&self.counters.bytes_fetched, text.len());
+
+        if let Some(cache) = &self.cache {
+            cache.put(url, &text)?;
+        }
         Ok(text)
     }
+
+    fn wait_turn(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let mut last_request = self.last_request.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(wait) = last_request.and_then(|at| interval.checked_sub(at.elapsed())) {
+            debug!("Rate limited, waiting {:?}", wait);
+            thread::sleep(wait);
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl TargetFailure {
+    fn new(stage: Stage, url: &reqwest::Url) -> Self {
+        Self {
+            stage,
+            url: url.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Fetch => write!(f, "fetch"),
+            Stage::Parse => write!(f, "parse"),
+        }
+    }
+}
+
+impl fmt::Display for TargetFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} `{}`", self.stage, self.url)
+    }
 }
@@ -1,27 +1,37 @@
 use crate::prelude::*;
+use crate::schema::Schemas;
 use crate::scrape::ScrapeTarget;
-use crate::types::Circuit;
+use crate::types::{Circuit, QualifyingResult, RaceResult, RaceSummary};
 
 pub struct RaceResultSummaryTarget {
     url: reqwest::Url,
+    year: u16,
 }
 
 impl RaceResultSummaryTarget {
     pub fn new(year: u16) -> Result<Self> {
         let url = format!("https://www.formula1.com/en/results.html/{year}/races.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self { url, year })
     }
 }
 
 impl ScrapeTarget for RaceResultSummaryTarget {
+    type Output = RaceSummary;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<RaceSummary> {
+        RaceSummary::parse_with(html, self.year, schemas)
+    }
 }
 
 pub struct RaceResultTarget {
     url: reqwest::Url,
+    year: u16,
+    circuit: Circuit,
 }
 
 impl RaceResultTarget {
@@ -30,12 +40,54 @@ impl RaceResultTarget {
         let circuit_name = &circuit.name;
         let url = format!("https://www.formula1.com/en/results.html/{year}/races/{circuit_idx}/{circuit_name}/race-result.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self {
+            url,
+            year,
+            circuit: circuit.clone(),
+        })
     }
 }
 
 impl ScrapeTarget for RaceResultTarget {
+    type Output = RaceResult;
+
+    fn request(&self) -> reqwest::blocking::Request {
+        reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
+    }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<RaceResult> {
+        RaceResult::parse_with(html, self.year, &self.circuit, schemas)
+    }
+}
+
+pub struct QualifyingResultTarget {
+    url: reqwest::Url,
+    year: u16,
+    circuit: Circuit,
+}
+
+impl QualifyingResultTarget {
+    pub fn new(year: u16, circuit: &Circuit) -> Result<Self> {
+        let circuit_idx = circuit.idx;
+        let circuit_name = &circuit.name;
+        let url = format!("https://www.formula1.com/en/results.html/{year}/races/{circuit_idx}/{circuit_name}/qualifying.html");
+        let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
+        Ok(Self {
+            url,
+            year,
+            circuit: circuit.clone(),
+        })
+    }
+}
+
+impl ScrapeTarget for QualifyingResultTarget {
+    type Output = QualifyingResult;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<QualifyingResult> {
+        QualifyingResult::parse_with(html, self.year, &self.circuit, schemas)
+    }
 }
@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::prelude::*;
+use crate::resolve::Resolver;
+use crate::scrape::{RaceResultSummaryTarget, RaceResultTarget, ScrapeTarget, Scraper};
+use crate::types::{Circuit, RaceResultEntry};
+
+type Select<'a> = Box<dyn FnMut(u16, &Resolver<Circuit>) -> Result<Vec<Circuit>> + 'a>;
+type Observe<'a> = Box<dyn FnMut(PageEvent) + 'a>;
+
+// A page scraped by `RaceResults`, reported to its observer
+#[derive(Debug, Clone, Copy)]
+pub enum PageEvent<'e> {
+    Started {
+        year: u16,
+        target: &'e str,
+    },
+    Finished {
+        year: u16,
+        target: &'e str,
+        ok: bool,
+    },
+    // the circuits of a season whose result pages will be scraped
+    Listed {
+        year: u16,
+        count: usize,
+    },
+}
+
+// A page of `RaceResults` which could not be scraped, the iteration goes on
+// with the next page
+#[derive(Debug)]
+pub struct ResultsError {
+    pub year: u16,
+    pub target: String,
+    // `None` when the season's summary failed
+    pub circuit: Option<Circuit>,
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for ResultsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}][{}] {:#}", self.year, self.target, self.error)
+    }
+}
+
+impl std::error::Error for ResultsError {}
+
+// Lazy iterator over the race results of a range of seasons, one page is
+// scraped at a time so only its rows are held in memory
+pub struct RaceResults<'a> {
+    scraper: &'a Scraper,
+    years: RangeInclusive<u16>,
+    select: Option<Select<'a>>,
+    observe: Option<Observe<'a>>,
+    // skip the cache lookup, refreshing the cached pages
+    fresh: bool,
+    circuits: VecDeque<(u16, Circuit)>,
+    rows: VecDeque<(u16, Circuit, RaceResultEntry)>,
+}
+
+impl Scraper {
+    // Race results of the seasons `year_min..=year_max`, in calendar order
+    pub fn results_between(&self, year_min: u16, year_max: u16) -> RaceResults<'_> {
+        RaceResults {
+            scraper: self,
+            years: year_min..=year_max,
+            select: None,
+            observe: None,
+            fresh: false,
+            circuits: VecDeque::new(),
+            rows: VecDeque::new(),
+        }
+    }
+}
+
+impl<'a> RaceResults<'a> {
+    // Pick the circuits to scrape from each season's summary, all by default
+    pub fn with_selection(
+        mut self,
+        select: impl FnMut(u16, &Resolver<Circuit>) -> Result<Vec<Circuit>> + 'a,
+    ) -> Self {
+        self.select = Some(Box::new(select));
+        self
+    }
+
+    pub fn with_observer(mut self, observe: impl FnMut(PageEvent) + 'a) -> Self {
+        self.observe = Some(Box::new(observe));
+        self
+    }
+
+    pub fn with_fresh_pages(mut self) -> Self {
+        self.fresh = true;
+        self
+    }
+
+    fn notify(&mut self, event: PageEvent) {
+        if let Some(observe) = self.observe.as_mut() {
+            observe(event);
+        }
+    }
+
+    fn scrape<T, O>(
+        &mut self,
+        year: u16,
+        target: &str,
+        circuit: Option<&Circuit>,
+        scrape_target: Result<T>,
+        then: impl FnOnce(&mut Self, T::Output) -> Result<O>,
+    ) -> Result<O, ResultsError>
+    where
+        T: ScrapeTarget,
+    {
+        self.notify(PageEvent::Started { year, target });
+        let result = scrape_target
+            .with_context(|| format!("create scrape target: {target} {year}"))
+            .and_then(|t| {
+                let output = match self.fresh {
+                    true => self.scraper.fetch_fresh(t),
+                    false => self.scraper.fetch(t),
+                };
+                output.with_context(|| format!("scrape: {target} {year}"))
+            })
+            .and_then(|output| then(self, output));
+        let ok = result.is_ok();
+        self.notify(PageEvent::Finished { year, target, ok });
+        result.map_err(|error| ResultsError {
+            year,
+            target: target.to_string(),
+            circuit: circuit.cloned(),
+            error,
+        })
+    }
+
+    fn scrape_summary(&mut self, year: u16) -> Result<(), ResultsError> {
+        let target = RaceResultSummaryTarget::new(year);
+        let circuits = self.scrape(year, "race summary", None, target, |this, summary| {
+            let resolver = summary.circuits()?;
+            match this.select.as_mut() {
+                Some(select) => select(year, &resolver),
+                None => Ok(resolver.items().cloned().collect()),
+            }
+        })?;
+        let count = circuits.len();
+        self.notify(PageEvent::Listed { year, count });
+        self.circuits
+            .extend(circuits.into_iter().map(|circuit| (year, circuit)));
+        Ok(())
+    }
+
+    fn scrape_result(&mut self, year: u16, circuit: Circuit) -> Result<(), ResultsError> {
+        let target = format!("race result {}", circuit.display_name);
+        let race_result = self.scrape(
+            year,
+            &target,
+            Some(&circuit),
+            RaceResultTarget::new(year, &circuit),
+            |_, race_result| Ok(race_result),
+        )?;
+        self.rows.extend(
+            race_result
+                .data
+                .into_iter()
+                .map(|entry| (year, circuit.clone(), entry)),
+        );
+        Ok(())
+    }
+}
+
+impl Iterator for RaceResults<'_> {
+    type Item = Result<(u16, Circuit, RaceResultEntry), ResultsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(Ok(row));
+            }
+            let scraped = match self.circuits.pop_front() {
+                Some((year, circuit)) => self.scrape_result(year, circuit),
+                None => {
+                    let year = self.years.next()?;
+                    self.scrape_summary(year)
+                }
+            };
+            if let Err(err) = scraped {
+                return Some(Err(err));
+            }
+        }
+    }
+}
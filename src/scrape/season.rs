@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use crate::schema::Schemas;
+use crate::scrape::ScrapeTarget;
+use crate::season::Seasons;
+
+// Landing page of the results archive, listing the available seasons
+pub struct SeasonListTarget {
+    url: reqwest::Url,
+}
+
+impl SeasonListTarget {
+    pub fn new() -> Result<Self> {
+        let url = "https://www.formula1.com/en/results.html";
+        let url = reqwest::Url::parse(url).with_context(|| format!("parse url: {url}"))?;
+        Ok(Self { url })
+    }
+}
+
+impl ScrapeTarget for SeasonListTarget {
+    type Output = Seasons;
+
+    fn request(&self) -> reqwest::blocking::Request {
+        reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
+    }
+
+    fn parse(&self, html: &str, _schemas: &Schemas) -> Result<Seasons> {
+        Seasons::parse(html)
+    }
+}
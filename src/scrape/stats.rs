@@ -0,0 +1,85 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+// Statistics of the requests executed by a `Scraper`
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScrapeStats {
+    pub pages_fetched: usize,
+    pub cache_hits: usize,
+    pub bytes_fetched: usize,
+    pub retries: usize,
+    pub parse_failures: usize,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct Counters {
+    pub pages_fetched: AtomicUsize,
+    pub cache_hits: AtomicUsize,
+    pub bytes_fetched: AtomicUsize,
+    pub retries: AtomicUsize,
+    pub parse_failures: AtomicUsize,
+}
+
+impl Counters {
+    pub fn add(counter: &AtomicUsize, n: usize) {
+        counter.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> ScrapeStats {
+        let get = |counter: &AtomicUsize| counter.load(Ordering::Relaxed);
+        ScrapeStats {
+            pages_fetched: get(&self.pages_fetched),
+            cache_hits: get(&self.cache_hits),
+            bytes_fetched: get(&self.bytes_fetched),
+            retries: get(&self.retries),
+            parse_failures: get(&self.parse_failures),
+        }
+    }
+}
+
+impl ScrapeStats {
+    pub fn pages(&self) -> usize {
+        self.pages_fetched + self.cache_hits
+    }
+}
+
+// Example: `12 pages fetched (1.4 MB), 30 cache hits, 1 retry, 0 parse failures`
+impl fmt::Display for ScrapeStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |n: usize, word: &str| match n {
+            1 => format!("{n} {word}"),
+            _ => format!("{n} {word}s"),
+        };
+        write!(
+            f,
+            "{} fetched ({}), {}, {}, {}",
+            plural(self.pages_fetched, "page"),
+            format_bytes(self.bytes_fetched),
+            plural(self.cache_hits, "cache hit"),
+            match self.retries {
+                1 => "1 retry".to_string(),
+                n => format!("{n} retries"),
+            },
+            plural(self.parse_failures, "parse failure"),
+        )
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["kB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1000.0 {
+            break;
+        }
+        value /= 1000.0;
+        unit = next;
+    }
+    match unit {
+        "B" => format!("{bytes} B"),
+        _ => format!("{value:.1} {unit}"),
+    }
+}
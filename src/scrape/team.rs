@@ -1,27 +1,37 @@
 use crate::prelude::*;
+use crate::schema::Schemas;
 use crate::scrape::ScrapeTarget;
-use crate::types::Team;
+use crate::types::{Team, TeamResult, TeamSummary};
 
 pub struct TeamResultSummaryTarget {
     url: reqwest::Url,
+    year: u16,
 }
 
 impl TeamResultSummaryTarget {
     pub fn new(year: u16) -> Result<Self> {
         let url = format!("https://www.formula1.com/en/results.html/{year}/team.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self { url, year })
     }
 }
 
 impl ScrapeTarget for TeamResultSummaryTarget {
+    type Output = TeamSummary;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<TeamSummary> {
+        TeamSummary::parse_with(html, self.year, schemas)
+    }
 }
 
 pub struct TeamResultTarget {
     url: reqwest::Url,
+    year: u16,
+    team: Team,
 }
 
 impl TeamResultTarget {
@@ -29,12 +39,22 @@ impl TeamResultTarget {
         let team_name = &team.name;
         let url = format!("https://www.formula1.com/en/results.html/{year}/team/{team_name}.html");
         let url = reqwest::Url::parse(&url).with_context(|| format!("parse url: {}", &url))?;
-        Ok(Self { url })
+        Ok(Self {
+            url,
+            year,
+            team: team.clone(),
+        })
     }
 }
 
 impl ScrapeTarget for TeamResultTarget {
+    type Output = TeamResult;
+
     fn request(&self) -> reqwest::blocking::Request {
         reqwest::blocking::Request::new(reqwest::Method::GET, self.url.clone())
     }
+
+    fn parse(&self, html: &str, schemas: &Schemas) -> Result<TeamResult> {
+        TeamResult::parse_with(html, self.year, &self.team, schemas)
+    }
 }
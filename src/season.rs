@@ -0,0 +1,82 @@
+use std::fmt;
+
+use chrono::Datelike;
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+use crate::prelude::*;
+
+pub const FIRST_SEASON: u16 = 1950;
+
+// Range of seasons offered by the results archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Seasons {
+    pub first: u16,
+    pub latest: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeasonNotAvailable {
+    pub year: u16,
+    pub seasons: Seasons,
+}
+
+impl Seasons {
+    const YEAR_SELECTOR_STR: &str = "a[data-name=\"year\"][data-value]";
+
+    // Read the season selector found on every results archive page
+    // Example:
+    //   <a href="/en/results.html/2023/races.html" data-name="year" data-value="2023"
+    //      class="resultsarchive-filter-item-link FilterTrigger selected">
+    pub fn parse(html: &str) -> Result<Self> {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse(Self::YEAR_SELECTOR_STR).unwrap();
+        let years: Vec<u16> = document
+            .select(&selector)
+            .filter_map(|elem| elem.value().attr("data-value")?.trim().parse().ok())
+            .collect();
+        match (years.iter().min(), years.iter().max()) {
+            (Some(first), Some(latest)) => Ok(Self {
+                first: *first,
+                latest: *latest,
+            }),
+            _ => Err(anyhow::anyhow!("find season selector in page")),
+        }
+    }
+
+    // Assume the current year's season is available, when the archive could not
+    // be reached
+    pub fn from_clock() -> Self {
+        let year = chrono::Utc::now().year();
+        Self {
+            first: FIRST_SEASON,
+            latest: u16::try_from(year).unwrap_or(u16::MAX),
+        }
+    }
+
+    pub fn contains(&self, year: u16) -> bool {
+        (self.first..=self.latest).contains(&year)
+    }
+
+    pub fn check(&self, year: u16) -> Result<u16, SeasonNotAvailable> {
+        match self.contains(year) {
+            true => Ok(year),
+            false => Err(SeasonNotAvailable {
+                year,
+                seasons: *self,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SeasonNotAvailable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "season {} not available: the archive covers {} to {}",
+            self.year, self.seasons.first, self.seasons.latest
+        )
+    }
+}
+
+impl std::error::Error for SeasonNotAvailable {}
@@ -1,14 +1,17 @@
+use std::fmt;
 use std::fmt::Debug;
 
 use anyhow::Context;
-use scraper::{ElementRef, Html};
+use scraper::Html;
+use serde::{Deserialize, Serialize};
 
-use crate::parse::HtmlTable;
+use crate::parse::{HtmlTable, TableReport};
 use crate::prelude::*;
+use crate::resolve::{fold, Resolver};
+use crate::schema::{PageKind, Record, Schemas};
+use crate::types::Nationality;
 
-use super::ScrapperHelper;
-
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct DriverResult {
     pub year: u16,
     pub driver: DriverFragment,
@@ -16,18 +19,33 @@ pub struct DriverResult {
 }
 
 impl DriverResult {
-    const TABLE_SELECTOR_STR: &str = "div.resultsarchive-wrapper>div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16, driver: &DriverFragment) -> Result<Self> {
+        Self::parse_with(html, year, driver, &Schemas::builtin())
+    }
+
+    pub fn parse_with(
+        html: &str,
+        year: u16,
+        driver: &DriverFragment,
+        schemas: &Schemas,
+    ) -> Result<Self> {
+        let schema = schemas.page(PageKind::DriverResult, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
-        // parse content
-        let data: Result<Vec<_>, _> = table.rows().map(|r| DriverResultEntry::parse(&r)).collect();
+        // parse rows
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| layout.record(&r).and_then(|r| DriverResultEntry::parse(&r)))
+            .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(Self {
@@ -36,9 +54,18 @@ impl DriverResult {
             data,
         })
     }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::DriverResult, year),
+            DriverResultEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct DriverResultEntry {
     pub grand_prix: String,
     pub date: String,
@@ -48,103 +75,128 @@ pub struct DriverResultEntry {
 }
 
 impl DriverResultEntry {
-    pub fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
+    pub(crate) fn parse(record: &Record) -> Result<Self> {
+        Ok(Self {
+            grand_prix: record.get("grand_prix")?,
+            date: record.get("date")?,
+            car: record.get("car")?,
+            pos: record.get("pos")?,
+            pts: record.get("pts")?,
+        })
+    }
 
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 5 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
+    // Classified finishing position, `None` for NC, DQ, DNS, DNQ, ...
+    pub fn position(&self) -> Option<u32> {
+        self.pos.trim().parse().ok()
+    }
 
-        let grand_prix = helper
-            .link(&cols[0])
-            .with_context(|| "column: grand_prix")?;
-        let date = helper
-            .inner_html(&cols[1])
-            .with_context(|| "column: date")?;
-        let car = helper.link(&cols[2]).with_context(|| "column: car")?;
-        let pos = helper.inner_html(&cols[3]).with_context(|| "column: pos")?;
-        let pts = helper.inner_html(&cols[4]).with_context(|| "column: pts")?;
+    pub fn points(&self) -> f32 {
+        self.pts.trim().parse().unwrap_or_default()
+    }
 
-        Ok(Self {
-            grand_prix,
-            date,
-            pos,
-            car,
-            pts,
-        })
+    pub fn started(&self) -> bool {
+        !matches!(
+            self.pos.trim().to_uppercase().as_str(),
+            "DNS" | "DNQ" | "DNPQ" | "DNP"
+        )
+    }
+
+    pub fn is_dnf(&self) -> bool {
+        self.pos.trim().eq_ignore_ascii_case("dnf")
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct DriverSummary {
     pub year: u16,
     pub data: Vec<DriverSummaryEntry>,
 }
 
 impl DriverSummary {
-    const TABLE_SELECTOR_STR: &str =
-    "div.resultsarchive-wrapper>div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16) -> Result<Self> {
+        Self::parse_with(html, year, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, schemas: &Schemas) -> Result<Self> {
+        let schema = schemas.page(PageKind::DriverSummary, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
         let data: Result<Vec<_>, _> = table
             .rows()
-            .map(|r| DriverSummaryEntry::parse(&r))
+            .map(|r| {
+                layout
+                    .record(&r)
+                    .and_then(|r| DriverSummaryEntry::parse(&r))
+            })
             .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(Self { year, data })
     }
+
+    // Look up the season's drivers by name, id or three-letter code
+    pub fn drivers(&self) -> Result<Resolver<DriverFragment>> {
+        let mut resolver = Resolver::new("driver");
+        for entry in &self.data {
+            let driver = entry.driver().with_context(|| {
+                format!(
+                    "obtain driver infos from summary data (driver: `{}`)",
+                    entry.driver
+                )
+            })?;
+            let mut keys = vec![
+                driver.display_name.clone(),
+                driver.name.clone(),
+                driver.id.clone(),
+                entry.driver_name.last.clone(),
+            ];
+            keys.extend(entry.driver_name.code.clone());
+            resolver.add(driver, &keys);
+        }
+        Ok(resolver)
+    }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::DriverSummary, year),
+            DriverSummaryEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct DriverSummaryEntry {
     pub pos: String,
     pub url: String,
     pub driver: String,
+    pub driver_name: DriverName,
     pub nationality: String,
     pub car: String,
     pub pts: String,
 }
 
 impl DriverSummaryEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
-
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 5 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
-
-        let pos = helper.inner_html(&cols[0]).with_context(|| "column: pos")?;
-        let driver_col = helper
-            .link_elem(&cols[1])
-            .with_context(|| "column: driver")?;
-        let url = helper.href(&driver_col).with_context(|| "column: driver")?;
-        let driver = helper
-            .join_spans(&driver_col)
-            .with_context(|| "column: driver")?;
-        let nationality = helper
-            .inner_html(&cols[2])
-            .with_context(|| "column: nationality")?;
-        let car = helper.link(&cols[3]).with_context(|| "column: car")?;
-        let pts = helper.inner_html(&cols[4]).with_context(|| "column: pts")?;
-
+    fn parse(record: &Record) -> Result<Self> {
         Ok(Self {
-            pos,
-            url,
-            driver,
-            nationality,
-            car,
-            pts,
+            pos: record.get("pos")?,
+            url: record.get("url")?,
+            driver: record.get("driver")?,
+            driver_name: record.driver_name("driver")?,
+            nationality: record.get("nationality")?,
+            car: record.get("car")?,
+            pts: record.get("pts")?,
         })
     }
 
@@ -164,9 +216,21 @@ impl DriverSummaryEntry {
 
         Ok(DriverFragment::new(id, name, &self.driver))
     }
+
+    pub fn position(&self) -> Option<u32> {
+        self.pos.trim().parse().ok()
+    }
+
+    pub fn points(&self) -> f32 {
+        self.pts.trim().parse().unwrap_or_default()
+    }
+
+    pub fn nationality(&self) -> Nationality {
+        Nationality::parse(&self.nationality)
+    }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct DriverFragment {
     pub id: String,
     pub name: String,
@@ -182,3 +246,61 @@ impl DriverFragment {
         }
     }
 }
+
+// Driver cell split in its parts, the three-letter code is missing on older seasons
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriverName {
+    pub first: String,
+    pub last: String,
+    pub code: Option<String>,
+}
+
+impl DriverName {
+    pub fn new<S: Into<String>>(first: S, last: S, code: Option<S>) -> Self {
+        Self {
+            first: first.into(),
+            last: last.into(),
+            code: code.map(Into::into),
+        }
+    }
+
+    // Split a plain `First Last` name, for cells without the per-part spans
+    pub fn from_full_name(name: &str) -> Self {
+        let mut tokens = name.split_whitespace();
+        let first = tokens.next().unwrap_or_default();
+        let last = tokens.collect::<Vec<_>>().join(" ");
+        Self::new(first.to_string(), last, None)
+    }
+
+    // Same driver when the folded full names agree, or the codes and last names
+    // do (the first name is spelled differently across some pages)
+    pub fn is_same(&self, other: &DriverName) -> bool {
+        if fold(&self.full_name()) == fold(&other.full_name()) {
+            return true;
+        }
+        match (&self.code, &other.code) {
+            (Some(code), Some(other_code)) => {
+                code.eq_ignore_ascii_case(other_code) && fold(&self.last) == fold(&other.last)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn full_name(&self) -> String {
+        match (self.first.is_empty(), self.last.is_empty()) {
+            (false, false) => format!("{} {}", self.first, self.last),
+            (true, _) => self.last.clone(),
+            (_, true) => self.first.clone(),
+        }
+    }
+}
+
+impl fmt::Display for DriverName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.full_name())?;
+        if let Some(code) = &self.code {
+            write!(f, " ({code})")?;
+        }
+        Ok(())
+    }
+}
@@ -1,75 +1,92 @@
 use std::fmt::Debug;
 
 use anyhow::Context;
-use scraper::{ElementRef, Html};
+use scraper::Html;
+use serde::Serialize;
 
-use crate::parse::HtmlTable;
+use crate::parse::{HtmlTable, TableReport};
 use crate::prelude::*;
+use crate::schema::{PageKind, Record, Schemas};
+use crate::types::DriverName;
 
-use super::ScrapperHelper;
-
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct FastestLapSummary {
     pub year: u16,
     pub data: Vec<FastestLapSummaryEntry>,
 }
 
 impl FastestLapSummary {
-    const TABLE_SELECTOR_STR: &str =
-        "div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16) -> Result<FastestLapSummary> {
+        Self::parse_with(html, year, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, schemas: &Schemas) -> Result<FastestLapSummary> {
+        let schema = schemas.page(PageKind::FastestLapSummary, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
         let data: Result<Vec<_>, _> = table
             .rows()
-            .map(|r| FastestLapSummaryEntry::parse(&r))
+            .map(|r| {
+                layout
+                    .record(&r)
+                    .and_then(|r| FastestLapSummaryEntry::parse(&r))
+            })
             .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(FastestLapSummary { year, data })
     }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::FastestLapSummary, year),
+            FastestLapSummaryEntry::parse,
+        )
+    }
+
+    // Fastest lap of the race at `round` (from 0) of the calendar. The summary
+    // follows the calendar order, but a Grand Prix name can appear more than once
+    // in a season so the row at the same index is preferred
+    pub fn for_round(&self, round: usize, grand_prix: &str) -> Option<&FastestLapSummaryEntry> {
+        let same_name = |entry: &&FastestLapSummaryEntry| {
+            entry.grand_prix.trim().to_lowercase() == grand_prix.trim().to_lowercase()
+        };
+        self.data
+            .get(round)
+            .filter(same_name)
+            .or_else(|| self.data.iter().find(same_name))
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct FastestLapSummaryEntry {
     pub grand_prix: String,
     pub driver: String,
+    pub driver_name: DriverName,
     pub car: String,
     pub time: String,
 }
 
 impl FastestLapSummaryEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
-
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 4 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
-
-        let grand_prix = helper
-            .inner_html(&cols[0])
-            .with_context(|| "column: grand_prix")?;
-        let driver = helper
-            .join_spans(&cols[1])
-            .with_context(|| "column: driver")?;
-        let car = helper.inner_html(&cols[2]).with_context(|| "column: car")?;
-        let time = helper
-            .inner_html(&cols[3])
-            .with_context(|| "column: time")?;
-
+    fn parse(record: &Record) -> Result<Self> {
         Ok(Self {
-            grand_prix,
-            driver,
-            car,
-            time,
+            grand_prix: record.get("grand_prix")?,
+            driver: record.get("driver")?,
+            driver_name: record.driver_name("driver")?,
+            car: record.get("car")?,
+            time: record.get("time")?,
         })
     }
 }
@@ -5,16 +5,22 @@ use crate::prelude::*;
 
 mod driver;
 mod fastestlap;
+mod nationality;
 mod race;
 mod team;
 
 pub use race::Circuit;
+pub use race::FastestLap;
+pub use race::QualifyingResult;
+pub use race::QualifyingResultEntry;
 pub use race::RaceResult;
 pub use race::RaceResultEntry;
 pub use race::RaceSummary;
 pub use race::RaceSummaryEntry;
+pub use race::Venue;
 
 pub use driver::DriverFragment;
+pub use driver::DriverName;
 pub use driver::DriverResult;
 pub use driver::DriverResultEntry;
 pub use driver::DriverSummary;
@@ -30,6 +36,8 @@ pub use team::TeamSummaryEntry;
 pub use fastestlap::FastestLapSummary;
 pub use fastestlap::FastestLapSummaryEntry;
 
+pub use nationality::Nationality;
+
 // use log::info;
 // info!(
 //     "\n=============================== inner_html \n{}\n=============================== inner_html",
@@ -37,14 +45,14 @@ pub use fastestlap::FastestLapSummaryEntry;
 // );
 
 #[derive(Debug)]
-struct ScrapperHelper {
+pub(crate) struct ScrapperHelper {
     selector_a: Selector,
     selector_td: Selector,
     selector_span: Selector,
 }
 
 impl ScrapperHelper {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             selector_a: Selector::parse("a").unwrap(),
             selector_td: Selector::parse("td").unwrap(),
@@ -52,12 +60,32 @@ impl ScrapperHelper {
         }
     }
 
-    fn inner_html(&self, elem: &ElementRef) -> Result<String> {
+    // Visible text of an element: entities decoded, whitespace collapsed and
+    // mobile-only elements (e.g. the driver code next to the full name) left out
+    pub(crate) fn text(&self, elem: &ElementRef) -> Result<String> {
+        let mut s = String::new();
+        for node in elem.descendants() {
+            let Some(text) = node.value().as_text() else {
+                continue;
+            };
+            let hidden = node
+                .ancestors()
+                .take_while(|parent| parent.id() != elem.id())
+                .filter_map(ElementRef::wrap)
+                .any(|parent| is_mobile_only(&parent));
+            if !hidden {
+                s.push_str(text);
+            }
+        }
+        Ok(s.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    pub(crate) fn inner_html(&self, elem: &ElementRef) -> Result<String> {
         let s = elem.inner_html().trim().to_string();
         Ok(s)
     }
 
-    fn link_elem<'a>(&'a self, elem: &'a ElementRef) -> Result<ElementRef> {
+    pub(crate) fn link_elem<'a>(&'a self, elem: &'a ElementRef) -> Result<ElementRef<'a>> {
         let s = elem
             .select(&self.selector_a)
             .next()
@@ -65,36 +93,68 @@ impl ScrapperHelper {
         Ok(s)
     }
 
-    fn link(&self, elem: &ElementRef) -> Result<String> {
-        let s = elem
-            .select(&self.selector_a)
-            .map(|x| x.inner_html())
-            .next()
-            .ok_or(anyhow::anyhow!("no <a> element found"))?
-            .trim()
-            .to_string();
-        Ok(s)
+    pub(crate) fn link(&self, elem: &ElementRef) -> Result<String> {
+        let link = self.link_elem(elem)?;
+        self.text(&link)
     }
 
-    fn join_spans(&self, elem: &ElementRef) -> Result<String> {
-        let s = elem
-            .select(&self.selector_span)
-            .map(|x| x.inner_html())
-            .collect::<Vec<String>>()
-            .join(" ")
-            .trim()
-            .to_string();
-        Ok(s)
+    pub(crate) fn join_spans(&self, elem: &ElementRef) -> Result<String> {
+        let mut spans = vec![];
+        for span in elem.select(&self.selector_span) {
+            if is_mobile_only(&span) {
+                continue;
+            }
+            let s = self.text(&span)?;
+            if !s.is_empty() {
+                spans.push(s);
+            }
+        }
+        Ok(spans.join(" "))
     }
 
-    fn table_cols<'a>(&'a self, elem: &'a ElementRef) -> impl Iterator<Item = ElementRef<'a>> {
+    pub(crate) fn table_cols<'a>(
+        &'a self,
+        elem: &'a ElementRef,
+    ) -> impl Iterator<Item = ElementRef<'a>> {
         elem.select(&self.selector_td).filter(|row| {
             !row.value()
                 .has_class("limiter", CaseSensitivity::AsciiCaseInsensitive)
         })
     }
 
-    fn href(&self, elem: &ElementRef) -> Result<String> {
+    // The archive renders the first name, last name and code in their own span,
+    // hidden depending on the screen size
+    pub(crate) fn driver_name(&self, elem: &ElementRef) -> Result<DriverName> {
+        let part = |class: &str| -> Result<Option<String>> {
+            let span = elem.select(&self.selector_span).find(|span| {
+                span.value()
+                    .has_class(class, CaseSensitivity::AsciiCaseInsensitive)
+            });
+            match span {
+                Some(span) => Ok(Some(self.text(&span)?).filter(|s| !s.is_empty())),
+                None => Ok(None),
+            }
+        };
+
+        let first = part("hide-for-tablet")?;
+        let last = part("hide-for-mobile")?;
+        let code = part("hide-for-desktop")?.map(|code| code.to_uppercase());
+        if first.is_none() && last.is_none() {
+            let name = self.text(elem)?;
+            if name.is_empty() {
+                return Err(anyhow::anyhow!("no driver name found"));
+            }
+            return Ok(DriverName::from_full_name(&name));
+        }
+
+        Ok(DriverName {
+            first: first.unwrap_or_default(),
+            last: last.unwrap_or_default(),
+            code,
+        })
+    }
+
+    pub(crate) fn href(&self, elem: &ElementRef) -> Result<String> {
         let s = elem
             .value()
             .attr("href")
@@ -104,3 +164,9 @@ impl ScrapperHelper {
         Ok(s)
     }
 }
+
+// The archive repeats some content for small screens, hidden on desktop
+fn is_mobile_only(elem: &ElementRef) -> bool {
+    elem.value()
+        .has_class("hide-for-desktop", CaseSensitivity::AsciiCaseInsensitive)
+}
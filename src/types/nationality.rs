@@ -0,0 +1,148 @@
+use std::fmt;
+
+use serde::Serialize;
+
+// Three-letter nationality codes used by the results archive, the IOC ones
+// (e.g. `NED`, `SUI`, `INA`) rather than ISO 3166-1 alpha-3.
+//
+// `iso` is the ISO 3166-1 alpha-2 code of the nation, or its ISO 3166-3 code for
+// former countries, and `country` the ISO 3166-1 alpha-2 code of the present day
+// country statistics are aggregated under.
+struct Nation {
+    code: &'static str,
+    iso: &'static str,
+    name: &'static str,
+    country: &'static str,
+    historical: bool,
+}
+
+impl Nation {
+    const fn new(code: &'static str, iso: &'static str, name: &'static str) -> Self {
+        Self {
+            code,
+            iso,
+            name,
+            country: iso,
+            historical: false,
+        }
+    }
+
+    const fn former(
+        code: &'static str,
+        iso: &'static str,
+        name: &'static str,
+        country: &'static str,
+    ) -> Self {
+        Self {
+            code,
+            iso,
+            name,
+            country,
+            historical: true,
+        }
+    }
+}
+
+const NATIONS: &[Nation] = &[
+    Nation::new("ARG", "AR", "Argentina"),
+    Nation::new("AUS", "AU", "Australia"),
+    Nation::new("AUT", "AT", "Austria"),
+    Nation::new("BEL", "BE", "Belgium"),
+    Nation::new("BRA", "BR", "Brazil"),
+    Nation::new("CAN", "CA", "Canada"),
+    Nation::new("CHI", "CL", "Chile"),
+    Nation::new("CHN", "CN", "China"),
+    Nation::new("COL", "CO", "Colombia"),
+    Nation::new("CZE", "CZ", "Czech Republic"),
+    Nation::new("DEN", "DK", "Denmark"),
+    Nation::new("ESP", "ES", "Spain"),
+    Nation::new("FIN", "FI", "Finland"),
+    Nation::new("FRA", "FR", "France"),
+    Nation::new("GBR", "GB", "United Kingdom"),
+    Nation::new("GER", "DE", "Germany"),
+    Nation::new("HKG", "HK", "Hong Kong"),
+    Nation::new("HUN", "HU", "Hungary"),
+    Nation::new("IND", "IN", "India"),
+    Nation::new("INA", "ID", "Indonesia"),
+    Nation::new("IRL", "IE", "Ireland"),
+    Nation::new("ITA", "IT", "Italy"),
+    Nation::new("JPN", "JP", "Japan"),
+    Nation::new("LIE", "LI", "Liechtenstein"),
+    Nation::new("MAR", "MA", "Morocco"),
+    Nation::new("MAS", "MY", "Malaysia"),
+    Nation::new("MEX", "MX", "Mexico"),
+    Nation::new("MON", "MC", "Monaco"),
+    Nation::new("NED", "NL", "Netherlands"),
+    Nation::new("NZL", "NZ", "New Zealand"),
+    Nation::new("POL", "PL", "Poland"),
+    Nation::new("POR", "PT", "Portugal"),
+    Nation::new("RSA", "ZA", "South Africa"),
+    Nation::new("RUS", "RU", "Russia"),
+    Nation::new("SUI", "CH", "Switzerland"),
+    Nation::new("SWE", "SE", "Sweden"),
+    Nation::new("THA", "TH", "Thailand"),
+    Nation::new("URU", "UY", "Uruguay"),
+    Nation::new("USA", "US", "United States"),
+    Nation::new("VEN", "VE", "Venezuela"),
+    Nation::new("ZIM", "ZW", "Zimbabwe"),
+    // Russian drivers raced under a neutral flag in 2021, listed after `RUS` so
+    // the country is named after the latter
+    Nation::new("RAF", "RU", "Russian Automobile Federation"),
+    // the Federal Republic kept its ISO code after the reunification
+    Nation::former("FRG", "DE", "West Germany", "DE"),
+    Nation::former("GDR", "DDDE", "East Germany", "DE"),
+    Nation::former("RHO", "RHZW", "Rhodesia", "ZW"),
+    Nation::former("URS", "SUHH", "Soviet Union", "RU"),
+];
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Nationality {
+    // code as found on the results archive
+    pub code: String,
+    pub iso: Option<String>,
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub historical: bool,
+}
+
+impl Nationality {
+    // Unknown codes are kept as-is, without ISO codes
+    pub fn parse(code: &str) -> Self {
+        let code = code.trim().to_uppercase();
+        match NATIONS.iter().find(|nation| nation.code == code) {
+            Some(nation) => Self {
+                code,
+                iso: Some(nation.iso.to_string()),
+                name: Some(nation.name.to_string()),
+                country: Some(nation.country.to_string()),
+                historical: nation.historical,
+            },
+            None => Self {
+                code,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.iso.is_some()
+    }
+
+    // Name of the present day country, e.g. `Germany` for West Germany
+    pub fn country_name(&self) -> Option<&'static str> {
+        let country = self.country.as_deref()?;
+        NATIONS
+            .iter()
+            .find(|nation| !nation.historical && nation.iso == country)
+            .map(|nation| nation.name)
+    }
+}
+
+impl fmt::Display for Nationality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
@@ -1,48 +1,80 @@
 use std::fmt::Debug;
 
 use anyhow::Context;
-use scraper::{ElementRef, Html};
+use chrono::{Datelike, Duration, NaiveDate};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 
-use crate::parse::HtmlTable;
+use crate::parse::{HtmlTable, TableReport};
 use crate::prelude::*;
+use crate::resolve::Resolver;
+use crate::schema::{PageKind, Record, Schemas};
+use crate::types::{DriverName, ScrapperHelper};
 
-use super::ScrapperHelper;
-
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct RaceResult {
     pub year: u16,
     pub circuit: Circuit,
+    // `None` when the page has no venue header
+    pub venue: Option<Venue>,
     pub data: Vec<RaceResultEntry>,
 }
 
 impl RaceResult {
-    const TABLE_SELECTOR_STR: &str = "div.resultsarchive-wrapper>div.resultsarchive-content>div.resultsarchive-col-right>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16, circuit: &Circuit) -> Result<Self> {
+        Self::parse_with(html, year, circuit, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, circuit: &Circuit, schemas: &Schemas) -> Result<Self> {
+        let schema = schemas.page(PageKind::RaceResult, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
-        let data: Result<Vec<_>, _> = table.rows().map(|r| RaceResultEntry::parse(&r)).collect();
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| layout.record(&r).and_then(|r| RaceResultEntry::parse(&r)))
+            .collect();
         let data = data.with_context(|| "parse table rows")?;
 
+        // parse header
+        let venue = schema
+            .header
+            .as_deref()
+            .and_then(|header| Venue::parse(&document_root, header));
+
         Ok(Self {
             year,
             circuit: circuit.clone(),
+            venue,
             data,
         })
     }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::RaceResult, year),
+            RaceResultEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RaceResultEntry {
     pub pos: String,
     pub no: String,
     pub driver: String,
+    pub driver_name: DriverName,
     pub car: String,
     pub laps: String,
     pub time_retired: String,
@@ -50,117 +82,117 @@ pub struct RaceResultEntry {
 }
 
 impl RaceResultEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
+    fn parse(record: &Record) -> Result<Self> {
+        Ok(Self {
+            pos: record.get("pos")?,
+            no: record.get("no")?,
+            driver: record.get("driver")?,
+            driver_name: record.driver_name("driver")?,
+            car: record.get("car")?,
+            laps: record.get("laps")?,
+            time_retired: record.get("time_retired")?,
+            pts: record.get("pts")?,
+        })
+    }
 
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 7 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
+    // Classified finishing position, `None` for NC, DQ, EX, DNS, ...
+    pub fn position(&self) -> Option<u32> {
+        self.pos.trim().parse().ok()
+    }
 
-        let pos = helper.inner_html(&cols[0]).with_context(|| "column: pos")?;
-        let no = helper.inner_html(&cols[1]).with_context(|| "column: no")?;
-        let driver = helper
-            .join_spans(&cols[2])
-            .with_context(|| "column: driver")?;
-        let car = helper.inner_html(&cols[3]).with_context(|| "column: car")?;
-        let laps = helper
-            .inner_html(&cols[4])
-            .with_context(|| "column: laps")?;
-        let time_retired = helper
-            .inner_html(&cols[5])
-            .with_context(|| "column: time_retired")?;
-        let pts = helper.inner_html(&cols[6]).with_context(|| "column:pts")?;
+    pub fn points(&self) -> f32 {
+        self.pts.trim().parse().unwrap_or_default()
+    }
 
-        Ok(Self {
-            pos,
-            no,
-            driver,
-            car,
-            laps,
-            time_retired,
-            pts,
-        })
+    // Retired drivers can still be classified when they covered enough laps
+    pub fn is_dnf(&self) -> bool {
+        self.time_retired.trim().eq_ignore_ascii_case("dnf")
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct RaceSummary {
     pub year: u16,
     pub data: Vec<RaceSummaryEntry>,
 }
 
 impl RaceSummary {
-    const TABLE_SELECTOR_STR: &str =
-        "div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16) -> Result<Self> {
+        Self::parse_with(html, year, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, schemas: &Schemas) -> Result<Self> {
+        let schema = schemas.page(PageKind::RaceSummary, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
-        let data: Result<Vec<_>, _> = table.rows().map(|r| RaceSummaryEntry::parse(&r)).collect();
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| layout.record(&r).and_then(|r| RaceSummaryEntry::parse(&r)))
+            .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(Self { year, data })
     }
+
+    // Look up the season's circuits by url slug or Grand Prix name
+    pub fn circuits(&self) -> Result<Resolver<Circuit>> {
+        let mut resolver = Resolver::new("grand prix");
+        for gp in &self.data {
+            let circuit = gp.circuit().with_context(|| {
+                format!(
+                    "obtain circuit infos from summary data (circuit: `{}`)",
+                    gp.grand_prix
+                )
+            })?;
+            let keys = [circuit.display_name.clone(), circuit.name.clone()];
+            resolver.add(circuit, &keys);
+        }
+        Ok(resolver)
+    }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::RaceSummary, year),
+            RaceSummaryEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct RaceSummaryEntry {
     pub grand_prix: String,
     pub url: String,
     pub date: String,
     pub winner: String,
+    pub winner_name: DriverName,
     pub car: String,
     pub laps: String,
     pub time: String,
 }
 
 impl RaceSummaryEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
-
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 6 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
-
-        let grand_prix = helper
-            .link(&cols[0])
-            .with_context(|| "column: grand prix")?;
-        let grand_prix_col = helper
-            .link_elem(&cols[0])
-            .with_context(|| "column: grand prix")?;
-        let url = helper
-            .href(&grand_prix_col)
-            .with_context(|| "column: grand prix")?;
-        let date = helper
-            .inner_html(&cols[1])
-            .with_context(|| "column: date")?;
-        let winner = helper
-            .join_spans(&cols[2])
-            .with_context(|| "column: winner")?;
-        let car = helper.inner_html(&cols[3]).with_context(|| "column: car")?;
-        let laps = helper
-            .inner_html(&cols[4])
-            .with_context(|| "column: laps")?;
-        let time = helper
-            .inner_html(&cols[5])
-            .with_context(|| "column: time")?;
-
+    fn parse(record: &Record) -> Result<Self> {
         Ok(Self {
-            grand_prix,
-            url,
-            date,
-            winner,
-            car,
-            laps,
-            time,
+            grand_prix: record.get("grand_prix")?,
+            url: record.get("url")?,
+            date: record.get("date")?,
+            winner: record.get("winner")?,
+            winner_name: record.driver_name("winner")?,
+            car: record.get("car")?,
+            laps: record.get("laps")?,
+            time: record.get("time")?,
         })
     }
 
@@ -188,7 +220,7 @@ impl RaceSummaryEntry {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Circuit {
     pub idx: u16,
     pub name: String,
@@ -205,7 +237,87 @@ impl Circuit {
     }
 }
 
-#[derive(Default, Debug)]
+// Where and when a Grand Prix took place, the same Grand Prix name can be held at
+// different circuits over the years
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct Venue {
+    pub circuit: String,
+    pub location: String,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl Venue {
+    // Example:
+    //   <p class="date">
+    //     <span class="start-date">03</span> - <span class="full-date">05 Mar 2023</span>
+    //     <span class="circuit-info">Bahrain International Circuit, Sakhir</span>
+    //   </p>
+    fn parse(root: &ElementRef, header: &str) -> Option<Self> {
+        let helper = ScrapperHelper::new();
+        let select = |selectors: &str| -> Option<String> {
+            let selector = Selector::parse(&format!("{header} {selectors}")).ok()?;
+            let elem = root.select(&selector).next()?;
+            helper.text(&elem).ok().filter(|s| !s.is_empty())
+        };
+
+        let circuit_info = select("span.circuit-info")?;
+        let (circuit, location) = match circuit_info.rsplit_once(',') {
+            Some((circuit, location)) => (circuit.trim(), location.trim()),
+            None => (circuit_info.as_str(), ""),
+        };
+
+        let end_date = select("span.full-date")
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%d %b %Y").ok());
+        let start_date = match (select("span.start-date"), end_date) {
+            (Some(start), Some(end)) => Self::parse_start_date(&start, end),
+            _ => None,
+        };
+
+        Some(Self {
+            circuit: circuit.to_string(),
+            location: location.to_string(),
+            start_date,
+            end_date,
+        })
+    }
+
+    // The start date only carries the parts which differ from the end date
+    // Example: `03`, `30 Apr`, `31 Dec 2021`
+    fn parse_start_date(start: &str, end: NaiveDate) -> Option<NaiveDate> {
+        let start = start.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(start, "%d %b %Y") {
+            return Some(date);
+        }
+        let date = match NaiveDate::parse_from_str(&format!("{start} {}", end.year()), "%d %b %Y") {
+            Ok(date) => date,
+            Err(_) => {
+                let day: u32 = start.parse().ok()?;
+                match day <= end.day() {
+                    true => end.with_day(day)?,
+                    // the race weekend started the month before
+                    false => (end - Duration::days(end.day().into())).with_day(day)?,
+                }
+            }
+        };
+        match date > end {
+            true => date.with_year(date.year() - 1),
+            false => Some(date),
+        }
+    }
+
+    // Stable key for the track, independent of the Grand Prix name
+    pub fn slug(&self) -> String {
+        self.circuit
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
+
+#[derive(Default, Debug, Serialize)]
 pub struct FastestLap {
     pub pos: String,
     pub no: String,
@@ -215,11 +327,82 @@ pub struct FastestLap {
     pub time: String,
 }
 
-#[derive(Default, Debug)]
-pub struct Qualifying {
+#[derive(Default, Debug, Serialize)]
+pub struct QualifyingResult {
+    pub year: u16,
+    pub circuit: Circuit,
+    pub data: Vec<QualifyingResultEntry>,
+}
+
+impl QualifyingResult {
+    pub fn parse(html: &str, year: u16, circuit: &Circuit) -> Result<Self> {
+        Self::parse_with(html, year, circuit, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, circuit: &Circuit, schemas: &Schemas) -> Result<Self> {
+        let schema = schemas.page(PageKind::QualifyingResult, year)?;
+
+        // parse html
+        let document = Html::parse_document(html);
+        let document_root = document.root_element();
+
+        // select table
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
+
+        // parse rows
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| {
+                layout
+                    .record(&r)
+                    .and_then(|r| QualifyingResultEntry::parse(&r))
+            })
+            .collect();
+        let data = data.with_context(|| "parse table rows")?;
+
+        Ok(Self {
+            year,
+            circuit: circuit.clone(),
+            data,
+        })
+    }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::QualifyingResult, year),
+            QualifyingResultEntry::parse,
+        )
+    }
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct QualifyingResultEntry {
     pub pos: String,
     pub no: String,
     pub driver: String,
+    pub driver_name: DriverName,
     pub car: String,
     pub time: String,
 }
+
+impl QualifyingResultEntry {
+    fn parse(record: &Record) -> Result<Self> {
+        Ok(Self {
+            pos: record.get("pos")?,
+            no: record.get("no")?,
+            driver: record.get("driver")?,
+            driver_name: record.driver_name("driver")?,
+            car: record.get("car")?,
+            time: record.get("time")?,
+        })
+    }
+
+    pub fn position(&self) -> Option<u32> {
+        self.pos.trim().parse().ok()
+    }
+}
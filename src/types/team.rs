@@ -1,14 +1,15 @@
 use std::fmt::Debug;
 
 use anyhow::Context;
-use scraper::{ElementRef, Html};
+use scraper::Html;
+use serde::Serialize;
 
-use crate::parse::HtmlTable;
+use crate::parse::{HtmlTable, TableReport};
 use crate::prelude::*;
+use crate::resolve::Resolver;
+use crate::schema::{PageKind, Record, Schemas};
 
-use super::ScrapperHelper;
-
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct TeamResult {
     pub year: u16,
     pub team: Team,
@@ -16,18 +17,28 @@ pub struct TeamResult {
 }
 
 impl TeamResult {
-    const TABLE_SELECTOR_STR: &str = "div.resultsarchive-wrapper>div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16, team: &Team) -> Result<Self> {
+        Self::parse_with(html, year, team, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, team: &Team, schemas: &Schemas) -> Result<Self> {
+        let schema = schemas.page(PageKind::TeamResult, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
-        let data: Result<Vec<_>, _> = table.rows().map(|r| TeamResultEntry::parse(&r)).collect();
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| layout.record(&r).and_then(|r| TeamResultEntry::parse(&r)))
+            .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(Self {
@@ -36,9 +47,18 @@ impl TeamResult {
             data,
         })
     }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::TeamResult, year),
+            TeamResultEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct TeamResultEntry {
     pub grand_prix: String,
     pub date: String,
@@ -46,57 +66,76 @@ pub struct TeamResultEntry {
 }
 
 impl TeamResultEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
-
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 3 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
-
-        let grand_prix = helper
-            .link(&cols[0])
-            .with_context(|| "column: grand prix")?;
-        let date = helper
-            .inner_html(&cols[1])
-            .with_context(|| "column: date")?;
-        let pts = helper.inner_html(&cols[2]).with_context(|| "column: pts")?;
-
+    fn parse(record: &Record) -> Result<Self> {
         Ok(Self {
-            grand_prix,
-            date,
-            pts,
+            grand_prix: record.get("grand_prix")?,
+            date: record.get("date")?,
+            pts: record.get("pts")?,
         })
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct TeamSummary {
     pub year: u16,
     pub data: Vec<TeamSummaryEntry>,
 }
 
 impl TeamSummary {
-    const TABLE_SELECTOR_STR: &str =
-        "div.resultsarchive-content>div.table-wrap>table.resultsarchive-table";
-
     pub fn parse(html: &str, year: u16) -> Result<TeamSummary> {
+        Self::parse_with(html, year, &Schemas::builtin())
+    }
+
+    pub fn parse_with(html: &str, year: u16, schemas: &Schemas) -> Result<TeamSummary> {
+        let schema = schemas.page(PageKind::TeamSummary, year)?;
+
         // parse html
         let document = Html::parse_document(html);
         let document_root = document.root_element();
 
         // select table
-        let table = HtmlTable::parse(&document_root, Self::TABLE_SELECTOR_STR)?;
+        let table = HtmlTable::parse(&document_root, &schema.table)?;
+
+        // locate columns
+        let layout = schema.layout(&table.header_names())?;
 
         // parse rows
-        let data: Result<Vec<_>, _> = table.rows().map(|r| TeamSummaryEntry::parse(&r)).collect();
+        let data: Result<Vec<_>, _> = table
+            .rows()
+            .map(|r| layout.record(&r).and_then(|r| TeamSummaryEntry::parse(&r)))
+            .collect();
         let data = data.with_context(|| "parse table rows")?;
 
         Ok(TeamSummary { year, data })
     }
+
+    // Look up the season's teams by url slug or name
+    pub fn teams(&self) -> Result<Resolver<Team>> {
+        let mut resolver = Resolver::new("team");
+        for entry in &self.data {
+            let team = entry.team().with_context(|| {
+                format!(
+                    "obtain team infos from summary data (team: `{}`)",
+                    entry.team
+                )
+            })?;
+            let keys = [team.display_name.clone(), team.name.clone()];
+            resolver.add(team, &keys);
+        }
+        Ok(resolver)
+    }
+
+    // Validate the page layout: table selector, headers and rows
+    pub fn check(html: &str, year: u16, schemas: &Schemas) -> TableReport {
+        TableReport::check(
+            html,
+            schemas.page(PageKind::TeamSummary, year),
+            TeamSummaryEntry::parse,
+        )
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct TeamSummaryEntry {
     pub pos: String,
     pub url: String,
@@ -105,25 +144,12 @@ pub struct TeamSummaryEntry {
 }
 
 impl TeamSummaryEntry {
-    fn parse(row: &ElementRef) -> Result<Self> {
-        let helper = ScrapperHelper::new();
-
-        let cols: Vec<_> = helper.table_cols(row).collect();
-        if cols.len() != 3 {
-            return Err(anyhow::anyhow!("invalid column count"));
-        }
-
-        let pos = helper.inner_html(&cols[0]).with_context(|| "column: pos")?;
-        let team_col = helper.link_elem(&cols[1]).with_context(|| "column: team")?;
-        let url = helper.href(&team_col).with_context(|| "column: team")?;
-        let team = helper.link(&cols[1]).with_context(|| "column: pts")?;
-        let pts = helper.inner_html(&cols[2]).with_context(|| "column: pts")?;
-
+    fn parse(record: &Record) -> Result<Self> {
         Ok(Self {
-            pos,
-            url,
-            team,
-            pts,
+            pos: record.get("pos")?,
+            url: record.get("url")?,
+            team: record.get("team")?,
+            pts: record.get("pts")?,
         })
     }
 
@@ -144,7 +170,7 @@ impl TeamSummaryEntry {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct Team {
     pub name: String,
     pub display_name: String,
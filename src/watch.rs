@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::diff::{diff_rows, ResultRow, RowChange};
+use crate::prelude::*;
+use crate::scrape::{ResultsError, Scraper};
+
+// Results of a season which changed since the previous poll
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchEvent {
+    pub year: u16,
+    pub detected_at: DateTime<Utc>,
+    pub changes: Vec<RowChange>,
+}
+
+// Outcome of a poll, the failed pages are polled again on the next one
+#[derive(Debug)]
+pub struct Poll {
+    // `None` when nothing changed
+    pub event: Option<WatchEvent>,
+    pub failures: Vec<ResultsError>,
+}
+
+// Race results of a season, polled to detect the newly published or corrected
+// ones
+#[derive(Debug)]
+pub struct Watch {
+    year: u16,
+    // `None` until the first poll
+    rows: Option<Vec<ResultRow>>,
+    // circuit index of the races whose page failed since the first poll, their
+    // rows join the baseline once scraped instead of being reported as added
+    missing: HashSet<u16>,
+}
+
+impl Watch {
+    pub fn new(year: u16) -> Self {
+        Self {
+            year,
+            rows: None,
+            missing: HashSet::new(),
+        }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    // Scrape the results again, bypassing the cache. The first poll sets the
+    // rows the next ones are compared with
+    pub fn poll(&mut self, scraper: &Scraper) -> Poll {
+        let mut rows = vec![];
+        let mut failures = vec![];
+        for row in scraper
+            .results_between(self.year, self.year)
+            .with_fresh_pages()
+        {
+            match row {
+                Ok((year, circuit, entry)) => rows.push(ResultRow::new(year, circuit, entry)),
+                Err(err) => failures.push(err),
+            }
+        }
+        self.update(rows, failures)
+    }
+
+    // Compare the rows of the pages which were scraped with the previous ones,
+    // the races whose page failed keep their previous rows, and the ones missing
+    // from the first poll are only compared once scraped
+    pub fn update(&mut self, mut rows: Vec<ResultRow>, failures: Vec<ResultsError>) -> Poll {
+        // without the summary, the races of the season are unknown
+        if failures.iter().any(|f| f.circuit.is_none()) {
+            return Poll {
+                event: None,
+                failures,
+            };
+        }
+        let failed: HashSet<_> = failures
+            .iter()
+            .filter_map(|f| f.circuit.as_ref().map(|c| c.idx))
+            .collect();
+        let Some(previous) = self.rows.take() else {
+            self.rows = Some(rows);
+            self.missing = failed;
+            return Poll {
+                event: None,
+                failures,
+            };
+        };
+
+        rows.extend(
+            previous
+                .iter()
+                .filter(|row| failed.contains(&row.circuit.idx))
+                .cloned(),
+        );
+        let compared: Vec<_> = rows
+            .iter()
+            .filter(|row| !self.missing.contains(&row.circuit.idx))
+            .cloned()
+            .collect();
+        let changes = diff_rows(&previous, &compared);
+        self.missing.retain(|idx| failed.contains(idx));
+        self.rows = Some(rows);
+        let event = (!changes.is_empty()).then(|| WatchEvent {
+            year: self.year,
+            detected_at: Utc::now(),
+            changes,
+        });
+        Poll { event, failures }
+    }
+
+    // Rows found by the last poll, `None` before the first one
+    pub fn rows(&self) -> Option<&[ResultRow]> {
+        self.rows.as_deref()
+    }
+}
+
+// Endpoint the watch events are posted to as JSON
+#[derive(Debug)]
+pub struct Webhook {
+    client: reqwest::blocking::Client,
+    url: reqwest::Url,
+}
+
+impl Webhook {
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(url: &str) -> Result<Self> {
+        let url = reqwest::Url::parse(url).with_context(|| format!("parse url: {url}"))?;
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Self::TIMEOUT)
+            .build()
+            .context("build webhook client")?;
+        Ok(Self { client, url })
+    }
+
+    pub fn send(&self, event: &WatchEvent) -> Result<()> {
+        let body = serde_json::to_string(event).context("serialize watch event")?;
+        self.client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("post watch event to `{}`", self.url))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::process::{Command, Output};
+
+use f1scraper::career::Career;
+use f1scraper::registry::DriverRegistry;
+use f1scraper::types::{
+    DriverFragment, DriverName, DriverResult, DriverResultEntry, DriverSummary, DriverSummaryEntry,
+    QualifyingResult, QualifyingResultEntry,
+};
+
+mod common;
+
+fn driver() -> DriverFragment {
+    DriverFragment {
+        id: "MICSCH01".to_string(),
+        name: "michael-schumacher".to_string(),
+        display_name: "Michael Schumacher".to_string(),
+    }
+}
+
+fn standing(pos: &str, pts: &str) -> DriverSummaryEntry {
+    DriverSummaryEntry {
+        pos: pos.to_string(),
+        url: "/en/results.html/2000/drivers/MICSCH01/michael-schumacher.html".to_string(),
+        driver: "Michael Schumacher MSC".to_string(),
+        driver_name: schumacher(),
+        car: "Ferrari".to_string(),
+        pts: pts.to_string(),
+        ..Default::default()
+    }
+}
+
+// The season's drivers, with another Schumacher
+fn registry() -> DriverRegistry {
+    let ralf = DriverSummaryEntry {
+        pos: "5".to_string(),
+        url: "/en/results.html/2000/drivers/RALSCH01/ralf-schumacher.html".to_string(),
+        driver: "Ralf Schumacher SCH".to_string(),
+        driver_name: DriverName::new("Ralf", "Schumacher", Some("SCH")),
+        ..Default::default()
+    };
+    let summary = DriverSummary {
+        year: 2000,
+        data: vec![standing("1", "108"), ralf],
+    };
+    DriverRegistry::from_summary(&summary).unwrap()
+}
+
+// Race by race rows: (car, pos)
+fn results(year: u16, rows: &[(&str, &str)]) -> DriverResult {
+    DriverResult {
+        year,
+        driver: driver(),
+        data: rows
+            .iter()
+            .map(|(car, pos)| DriverResultEntry {
+                car: car.to_string(),
+                pos: pos.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn qualifying(idx: u16, pole: DriverName, display: &str) -> QualifyingResult {
+    QualifyingResult {
+        year: 2000,
+        circuit: common::circuit(idx),
+        data: vec![QualifyingResultEntry {
+            pos: "1".to_string(),
+            driver: display.to_string(),
+            driver_name: pole,
+            ..Default::default()
+        }],
+    }
+}
+
+fn schumacher() -> DriverName {
+    DriverName::new("Michael", "Schumacher", Some("MSC"))
+}
+
+#[test]
+fn counts_wins_podiums_starts_and_dnfs() {
+    let mut career = Career::new(&driver());
+    career.add_season(
+        &standing("1", "108"),
+        &results(
+            2000,
+            &[
+                ("Ferrari", "1"),
+                ("Ferrari", "3"),
+                ("Ferrari", "DNF"),
+                ("Ferrari", "NC"),
+                ("Ferrari", "DNS"),
+                ("Ferrari", "2"),
+            ],
+        ),
+        None,
+        &registry(),
+    );
+    career.add_season(
+        &standing("4", "44"),
+        &results(
+            1999,
+            &[("Ferrari", "DNF"), ("Ferrari", "1"), ("Ferrari", "5")],
+        ),
+        None,
+        &registry(),
+    );
+
+    let first = &career.seasons[0];
+    assert_eq!(first.starts, 5);
+    assert_eq!(first.wins, 1);
+    assert_eq!(first.podiums, 3);
+    assert_eq!(first.dnfs, 1);
+    assert_eq!(first.poles, None);
+
+    let totals = career.totals();
+    assert_eq!(totals.seasons, 2);
+    assert_eq!(totals.starts, 8);
+    assert_eq!(totals.wins, 2);
+    assert_eq!(totals.podiums, 4);
+    assert_eq!(totals.dnfs, 2);
+    assert_eq!(totals.pts, 152.0);
+    assert_eq!(totals.championships, 1);
+    assert_eq!(totals.best_championship_pos, Some(1));
+    assert_eq!(totals.poles, None);
+    assert_eq!(totals.teams, ["Ferrari"]);
+}
+
+#[test]
+fn resolves_pole_sitters_to_the_driver_id() {
+    let qualifying = [
+        // same driver, the cell shows the code on its own line
+        qualifying(1, schumacher(), "Michael\nSchumacher\nMSC"),
+        // the case is folded
+        qualifying(2, DriverName::new("MICHAEL", "SCHUMACHER", None), "MSC"),
+        // the code and last name agree with a differently spelled first name
+        qualifying(
+            3,
+            DriverName::new("M.", "Schumacher", Some("msc")),
+            "M. Schumacher",
+        ),
+        // another Schumacher with the same display string
+        qualifying(
+            4,
+            DriverName::new("Ralf", "Schumacher", Some("SCH")),
+            "Michael Schumacher MSC",
+        ),
+        qualifying(
+            5,
+            DriverName::new("Mika", "Häkkinen", Some("HAK")),
+            "Mika Häkkinen",
+        ),
+    ];
+
+    let mut career = Career::new(&driver());
+    career.add_season(
+        &standing("1", "108"),
+        &results(2000, &[("Ferrari", "1")]),
+        Some(&qualifying),
+        &registry(),
+    );
+    career.add_season(
+        &standing("4", "44"),
+        &results(1999, &[("Ferrari", "1")]),
+        Some(&[]),
+        &registry(),
+    );
+
+    assert_eq!(career.seasons[0].poles, Some(3));
+    // an empty qualifying list means nothing was scraped for the season
+    assert_eq!(career.seasons[1].poles, None);
+    assert_eq!(career.totals().poles, Some(3));
+}
+
+#[test]
+fn resolves_the_driver_like_the_result_commands() {
+    let dir = std::env::temp_dir().join(format!("f1scraper-career-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    common::fixture_cache(&dir);
+    let career = |name: &str| -> Output {
+        Command::new(env!("CARGO_BIN_EXE_f1scraper"))
+            .args(["-v", "--cache-dir", dir.to_str().unwrap()])
+            .args(["--no-progress", "--retries", "0", "--timeout", "2"])
+            .args(["driver", "career", name, "--year", "2007"])
+            .env("XDG_CONFIG_HOME", &dir)
+            .output()
+            .unwrap()
+    };
+    let stderr = |output: Output| String::from_utf8_lossy(&output.stderr).to_string();
+
+    // the three-letter code, and a typo
+    for name in ["RAI", "raikonen"] {
+        let stderr = stderr(career(name));
+        assert!(
+            stderr.contains("[2007] found driver: DriverFragment { id: \"KIMRAI01\""),
+            "{name}: {stderr}"
+        );
+    }
+
+    let output = career("nobody");
+    assert!(!output.status.success());
+    let stderr = stderr(output);
+    assert!(
+        stderr.contains("find driver between `2007` and `2007` with name: nobody"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("did you mean"), "{stderr}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,45 @@
+// Each test crate uses its own subset of the helpers
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use f1scraper::scrape::ResponseCache;
+use f1scraper::types::Circuit;
+
+const ARCHIVE: &str = "https://www.formula1.com/en/results.html";
+
+// Fill an on-disk cache with the fixture pages of the 2007 season, so that the
+// binary runs against it without reaching the website
+pub fn fixture_cache(dir: &Path) {
+    let cache = ResponseCache::on_disk(dir);
+    let pages = [
+        ("", "seasons.html"),
+        ("/2007/races.html", "races.html"),
+        (
+            "/2007/races/1/australia/race-result.html",
+            "race-result.html",
+        ),
+        ("/2007/drivers.html", "drivers.html"),
+        ("/2007/team.html", "team.html"),
+        ("/2007/fastest-laps.html", "fastest-laps.html"),
+    ];
+    for (path, fixture) in pages {
+        let html = std::fs::read_to_string(format!("tests/fixtures/2007/{fixture}")).unwrap();
+        put_page(&cache, path, &html);
+    }
+}
+
+// Cache a page of the archive, e.g. `/2007/races.html`
+pub fn put_page(cache: &ResponseCache, path: &str, html: &str) {
+    let url = reqwest::Url::parse(&format!("{ARCHIVE}{path}")).unwrap();
+    cache.put(&url, html).unwrap();
+}
+
+// Race `idx` of a season, for the tests building results by hand
+pub fn circuit(idx: u16) -> Circuit {
+    Circuit {
+        idx,
+        name: format!("circuit-{idx}"),
+        display_name: format!("Circuit {idx}"),
+    }
+}
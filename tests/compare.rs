@@ -0,0 +1,156 @@
+use f1scraper::compare::{HeadToHead, TeammateReport};
+use f1scraper::types::{QualifyingResult, QualifyingResultEntry, RaceResult, RaceResultEntry};
+
+mod common;
+
+// Rows in classification order: (driver, car, pts, time or retired)
+fn race(idx: u16, rows: &[(&str, &str, &str, &str)]) -> RaceResult {
+    RaceResult {
+        year: 2010,
+        circuit: common::circuit(idx),
+        venue: None,
+        data: rows
+            .iter()
+            .enumerate()
+            .map(|(pos, (driver, car, pts, time_retired))| RaceResultEntry {
+                pos: (pos + 1).to_string(),
+                driver: driver.to_string(),
+                car: car.to_string(),
+                pts: pts.to_string(),
+                time_retired: time_retired.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn qualifying(idx: u16, drivers: &[&str]) -> QualifyingResult {
+    QualifyingResult {
+        year: 2010,
+        circuit: common::circuit(idx),
+        data: drivers
+            .iter()
+            .enumerate()
+            .map(|(pos, driver)| QualifyingResultEntry {
+                pos: (pos + 1).to_string(),
+                driver: driver.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+fn pair<'a>(report: &'a TeammateReport, car: &str) -> &'a HeadToHead {
+    let team = report.teams.iter().find(|t| t.car == car).unwrap();
+    assert_eq!(team.pairs.len(), 1);
+    &team.pairs[0]
+}
+
+#[test]
+fn pairs_keep_their_orientation_across_races() {
+    let races = [
+        race(
+            1,
+            &[
+                ("Button", "McLaren", "25", ""),
+                ("Hamilton", "McLaren", "18", ""),
+            ],
+        ),
+        race(
+            2,
+            &[
+                ("Hamilton", "McLaren", "25", ""),
+                ("Button", "McLaren", "0", "DNF"),
+            ],
+        ),
+        race(
+            3,
+            &[
+                ("Hamilton", "McLaren", "25", ""),
+                ("Button", "McLaren", "18", ""),
+            ],
+        ),
+    ];
+    let report = TeammateReport::build(&races, &[]);
+    assert_eq!(report.year, 2010);
+
+    let pair = pair(&report, "McLaren");
+    assert_eq!(pair.races, 3);
+    assert_eq!(pair.first.driver, "Button");
+    assert_eq!(pair.second.driver, "Hamilton");
+    assert_eq!((pair.first.race_ahead, pair.second.race_ahead), (1, 2));
+    assert_eq!((pair.first.pts, pair.second.pts), (43.0, 68.0));
+    assert_eq!((pair.first.dnfs, pair.second.dnfs), (1, 0));
+    assert!((pair.first.points_share + pair.second.points_share - 1.0).abs() < 1e-6);
+    // no qualifying results
+    assert_eq!(pair.qualifying_sessions, 0);
+    assert_eq!(pair.first.qualifying_ahead, None);
+}
+
+#[test]
+fn shared_drives_count_once() {
+    // Fangio took over Musso's car after retiring his own
+    let races = [race(
+        1,
+        &[
+            ("Fangio", "Ferrari", "4", ""),
+            ("Musso", "Ferrari", "4", ""),
+            ("Collins", "Ferrari", "0", ""),
+            ("Fangio", "Ferrari", "0", "DNF"),
+        ],
+    )];
+    let report = TeammateReport::build(&races, &[]);
+    let pairs = &report.teams[0].pairs;
+    assert_eq!(pairs.len(), 3);
+    // ordered by driver names
+    let fangio_collins = &pairs[0];
+    assert_eq!(fangio_collins.first.driver, "Collins");
+    assert_eq!(fangio_collins.second.driver, "Fangio");
+    assert_eq!(pairs[2].first.driver, "Fangio");
+    assert_eq!(pairs[2].second.driver, "Musso");
+    assert_eq!(fangio_collins.races, 1);
+    // the best placed row counts
+    assert_eq!(fangio_collins.second.race_ahead, 1);
+    assert_eq!(fangio_collins.second.pts, 4.0);
+    assert_eq!(fangio_collins.second.dnfs, 0);
+}
+
+#[test]
+fn qualifying_is_matched_to_races_by_circuit() {
+    let races = [
+        race(
+            1,
+            &[
+                ("Alonso", "Ferrari", "25", ""),
+                ("Massa", "Ferrari", "18", ""),
+            ],
+        ),
+        race(
+            2,
+            &[
+                ("Alonso", "Ferrari", "25", ""),
+                ("Massa", "Ferrari", "18", ""),
+            ],
+        ),
+        race(
+            3,
+            &[
+                ("Alonso", "Ferrari", "25", ""),
+                ("Massa", "Ferrari", "18", ""),
+            ],
+        ),
+    ];
+    // out of order, and missing for the second race
+    let qualifying = [
+        qualifying(3, &["Alonso", "Massa"]),
+        qualifying(1, &["Massa", "Alonso"]),
+    ];
+    let report = TeammateReport::build(&races, &qualifying);
+
+    let pair = pair(&report, "Ferrari");
+    assert_eq!(pair.races, 3);
+    assert_eq!(pair.qualifying_sessions, 2);
+    assert_eq!(pair.first.qualifying_ahead, Some(1));
+    assert_eq!(pair.second.qualifying_ahead, Some(1));
+    assert_eq!(pair.first.race_ahead, 3);
+}
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use f1scraper::scrape::ResponseCache;
+
+mod common;
+
+// Runs of `f1scraper race result` over the fixture cache, with the config file
+// `$XDG_CONFIG_HOME/f1scraper/config.toml`
+struct Env {
+    dir: PathBuf,
+}
+
+impl Env {
+    fn new(name: &str, config: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("f1scraper-config-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        common::fixture_cache(&dir.join("cache"));
+        std::fs::create_dir_all(dir.join("f1scraper")).unwrap();
+        std::fs::write(dir.join("f1scraper").join("config.toml"), config).unwrap();
+        Self { dir }
+    }
+
+    fn run(&self, args: &[&str], env: &[(&str, &str)]) -> Output {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_f1scraper"));
+        cmd.args(["--cache-dir", self.dir.join("cache").to_str().unwrap()])
+            .args(["--retries", "0", "--timeout", "2"])
+            .args(["race", "result", "--year", "2007"])
+            // global flags are accepted after the command too
+            .args(args)
+            .env("XDG_CONFIG_HOME", &self.dir);
+        for var in [
+            "F1SCRAPER_CONFIG",
+            "F1SCRAPER_PROFILE",
+            "F1SCRAPER_FORMAT",
+            "F1SCRAPER_KEEP_GOING",
+            "F1SCRAPER_NO_PROGRESS",
+            "F1SCRAPER_NO_COMPRESSION",
+        ] {
+            cmd.env_remove(var);
+        }
+        cmd.envs(env.iter().copied()).output().unwrap()
+    }
+
+    // The format of the printed rows
+    fn format(&self, args: &[&str], env: &[(&str, &str)]) -> &'static str {
+        let output = self.run(args, env);
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        match stdout.chars().next() {
+            Some('[') => "debug",
+            Some('{') => "ndjson",
+            Some('y') if stdout.starts_with("year,") => "csv",
+            _ => panic!("unexpected output: {stdout}"),
+        }
+    }
+}
+
+impl Drop for Env {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn flags_take_precedence_over_env_profile_and_defaults() {
+    let env = Env::new("empty", "");
+    assert_eq!(env.format(&[], &[]), "debug");
+
+    let env = Env::new(
+        "precedence",
+        r#"
+[default]
+format = "csv"
+
+[profiles.json]
+format = "ndjson"
+"#,
+    );
+    let profile = ["--profile", "json"];
+    assert_eq!(env.format(&[], &[]), "csv");
+    assert_eq!(env.format(&profile, &[]), "ndjson");
+    assert_eq!(env.format(&[], &[("F1SCRAPER_PROFILE", "json")]), "ndjson");
+    assert_eq!(
+        env.format(&profile, &[("F1SCRAPER_FORMAT", "debug")]),
+        "debug"
+    );
+    assert_eq!(
+        env.format(
+            &["--profile", "json", "--format", "csv"],
+            &[("F1SCRAPER_FORMAT", "debug")]
+        ),
+        "csv"
+    );
+
+    // the profile must exist
+    let output = env.run(&["--profile", "missing"], &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("available profiles: json"));
+}
+
+#[test]
+fn boolean_env_vars() {
+    let env = Env::new("bool", "");
+    let progress = |value| {
+        let output = env.run(
+            &[],
+            &[
+                ("F1SCRAPER_KEEP_GOING", value),
+                ("F1SCRAPER_NO_PROGRESS", value),
+                ("F1SCRAPER_NO_COMPRESSION", value),
+            ],
+        );
+        assert!(output.status.success(), "{value}: {output:?}");
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Progress:")
+    };
+    for value in ["1", "true", "yes", "on"] {
+        assert!(!progress(value), "{value}");
+    }
+    for value in ["0", "false", "no", "off"] {
+        assert!(progress(value), "{value}");
+    }
+
+    let output = env.run(&[], &[("F1SCRAPER_KEEP_GOING", "maybe")]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn env_vars_and_flags_turn_off_config_booleans() {
+    let env = Env::new(
+        "bool-config",
+        r#"
+[default]
+keep_going = true
+progress = false
+"#,
+    );
+    // a page failing to parse, reported at the end when keeping going
+    common::put_page(
+        &ResponseCache::on_disk(env.dir.join("cache")),
+        "/2007/races/1/australia/race-result.html",
+        "<html><body>Not found</body></html>",
+    );
+    let run = |args: &[&str], vars: &[(&str, &str)]| {
+        let output = env.run(args, vars);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(!output.status.success());
+        (
+            stderr.contains("Error report:"),
+            stderr.contains("Progress:"),
+        )
+    };
+
+    assert_eq!(run(&[], &[]), (true, false));
+    let vars = [
+        ("F1SCRAPER_KEEP_GOING", "false"),
+        ("F1SCRAPER_NO_PROGRESS", "false"),
+    ];
+    assert_eq!(run(&[], &vars), (false, true));
+    assert_eq!(
+        run(&["--keep-going=false", "--no-progress=false"], &[]),
+        (false, true)
+    );
+    // flags take precedence over the env vars
+    assert_eq!(
+        run(&["--keep-going", "--no-progress"], &vars),
+        (true, false)
+    );
+}
@@ -0,0 +1,94 @@
+use f1scraper::diff::{diff_rows, FieldChange, ResultRow, RowChange};
+
+fn rows(ndjson: &str) -> Vec<ResultRow> {
+    ResultRow::read_all(ndjson.as_bytes()).unwrap()
+}
+
+const BAHRAIN: &str =
+    r#""year":2023,"circuit":{"idx":1141,"name":"bahrain","display_name":"Bahrain"}"#;
+
+fn row(driver: &str, pos: &str, pts: &str) -> String {
+    format!(
+        r#"{{{BAHRAIN},"pos":"{pos}","no":"1","driver":"{driver}","driver_name":{{"first":"","last":"","code":null}},"car":"Red Bull","laps":"57","time_retired":"","pts":"{pts}"}}"#
+    )
+}
+
+#[test]
+fn reads_rows_printed_as_ndjson() {
+    let old = rows(&format!(
+        "{}\n\n{}\n",
+        row("A", "1", "25"),
+        row("B", "2", "18")
+    ));
+    assert_eq!(old.len(), 2);
+    assert_eq!(old[1].circuit.name, "bahrain");
+    assert_eq!(old[1].entry.pts, "18");
+
+    let json = serde_json::to_string(&old[0]).unwrap();
+    assert_eq!(rows(&json), old[..1]);
+}
+
+#[test]
+fn reports_changed_removed_and_added_rows() {
+    let old = rows(
+        &[
+            row("A", "1", "25"),
+            row("B", "2", "18"),
+            row("C", "3", "15"),
+        ]
+        .join("\n"),
+    );
+    // A is penalized behind B, C is disqualified and D reclassified
+    let new = rows(
+        &[
+            row("B", "1", "25"),
+            row("A", "2", "18"),
+            row("D", "3", "15"),
+        ]
+        .join("\n"),
+    );
+
+    let changes = diff_rows(&old, &new);
+    let fields = |old_pos: &str, new_pos: &str, old_pts: &str, new_pts: &str| {
+        vec![
+            FieldChange {
+                field: "pos",
+                old: old_pos.to_string(),
+                new: new_pos.to_string(),
+            },
+            FieldChange {
+                field: "pts",
+                old: old_pts.to_string(),
+                new: new_pts.to_string(),
+            },
+        ]
+    };
+    assert_eq!(changes.len(), 4);
+    assert!(
+        matches!(&changes[0], RowChange::Changed { driver, fields: f, .. }
+        if driver == "A" && *f == fields("1", "2", "25", "18"))
+    );
+    assert!(
+        matches!(&changes[1], RowChange::Changed { driver, fields: f, .. }
+        if driver == "B" && *f == fields("2", "1", "18", "25"))
+    );
+    assert!(matches!(&changes[2], RowChange::Removed { row } if row.entry.driver == "C"));
+    assert!(matches!(&changes[3], RowChange::Added { row } if row.entry.driver == "D"));
+
+    assert!(diff_rows(&old, &old).is_empty());
+}
+
+#[test]
+fn tells_shared_drives_apart() {
+    // the same driver classified twice, in their own car and in a shared one
+    let old = rows(&[row("A", "1", "8"), row("B", "2", "6"), row("A", "3", "2")].join("\n"));
+    let new = rows(&[row("A", "1", "8"), row("B", "2", "6"), row("A", "4", "1.5")].join("\n"));
+
+    let changes = diff_rows(&old, &new);
+    assert_eq!(changes.len(), 1);
+    assert!(
+        matches!(&changes[0], RowChange::Changed { driver, fields, .. }
+        if driver == "A" && fields.len() == 2 && fields[0].old == "3" && fields[0].new == "4")
+    );
+    assert!(diff_rows(&old, &old).is_empty());
+}
@@ -0,0 +1,114 @@
+use f1scraper::history::CircuitHistory;
+use f1scraper::types::{Circuit, FastestLapSummary, FastestLapSummaryEntry, Venue};
+
+fn race(name: &str, display_name: &str, venue: Option<(&str, &str)>) -> (Circuit, Option<Venue>) {
+    let circuit = Circuit {
+        idx: 1,
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+    };
+    let venue = venue.map(|(circuit, location)| Venue {
+        circuit: circuit.to_string(),
+        location: location.to_string(),
+        start_date: None,
+        end_date: None,
+    });
+    (circuit, venue)
+}
+
+// Indices of the races of a season matching the query
+fn matching(query: &str, races: &[(Circuit, Option<Venue>)]) -> Vec<usize> {
+    let races: Vec<_> = races
+        .iter()
+        .map(|(circuit, venue)| (circuit, venue.as_ref()))
+        .collect();
+    CircuitHistory::new(query).matching(&races)
+}
+
+#[test]
+fn matches_races_like_the_resolver() {
+    let season = [
+        race("bahrain", "Bahrain", None),
+        race(
+            "brazil",
+            "Brazil",
+            Some(("Autódromo José Carlos Pace", "São Paulo")),
+        ),
+        race(
+            "great-britain",
+            "Great Britain",
+            Some(("Silverstone Circuit", "Silverstone")),
+        ),
+        race(
+            "monaco",
+            "Monaco",
+            Some(("Circuit de Monaco", "Monte Carlo")),
+        ),
+    ];
+
+    let cases: [(&str, &[usize]); 10] = [
+        ("monaco", &[3]),
+        ("Great Britain", &[2]),
+        ("great-britain", &[2]),
+        // accents are folded
+        ("sao paulo", &[1]),
+        ("autodromo jose", &[1]),
+        ("silverstone", &[2]),
+        ("brit", &[2]),
+        ("monacco", &[3]),
+        // too short to match partially
+        ("m", &[]),
+        ("hungary", &[]),
+    ];
+    for (query, expected) in cases {
+        assert_eq!(matching(query, &season), expected, "{query}");
+    }
+}
+
+#[test]
+fn matches_every_race_held_at_a_venue() {
+    let season = [
+        race("austria", "Austria", Some(("Red Bull Ring", "Spielberg"))),
+        race("styria", "Styria", Some(("Red Bull Ring", "Spielberg"))),
+        race("hungary", "Hungary", Some(("Hungaroring", "Budapest"))),
+    ];
+    assert_eq!(matching("spielberg", &season), [0, 1]);
+    assert_eq!(matching("red bull", &season), [0, 1]);
+    assert_eq!(matching("styria", &season), [1]);
+    // without the venues, only the Grand Prix names match
+    let season: Vec<_> = season
+        .into_iter()
+        .map(|(circuit, _)| (circuit, None))
+        .collect();
+    assert!(matching("spielberg", &season).is_empty());
+}
+
+#[test]
+fn finds_the_fastest_lap_of_a_race_by_its_round() {
+    let lap = |grand_prix: &str, driver: &str| FastestLapSummaryEntry {
+        grand_prix: grand_prix.to_string(),
+        driver: driver.to_string(),
+        ..Default::default()
+    };
+    let fastest_laps = FastestLapSummary {
+        year: 2020,
+        data: vec![
+            lap("Austria", "Lando Norris"),
+            lap("Austria", "Carlos Sainz"),
+            lap("Hungary", "Lewis Hamilton"),
+            lap("Great Britain", "Max Verstappen"),
+        ],
+    };
+    let driver = |round, grand_prix| {
+        fastest_laps
+            .for_round(round, grand_prix)
+            .map(|fl| fl.driver.as_str())
+    };
+
+    // the same Grand Prix twice in a season
+    assert_eq!(driver(0, "Austria"), Some("Lando Norris"));
+    assert_eq!(driver(1, "Austria"), Some("Carlos Sainz"));
+    // rows out of calendar order are found by name
+    assert_eq!(driver(4, "great britain"), Some("Max Verstappen"));
+    assert_eq!(driver(2, "Styria"), None);
+}
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use f1scraper::scrape::ResponseCache;
+
+mod common;
+
+// The fixture cache of the 2007 season, with a page which fails to parse
+struct Broken {
+    dir: PathBuf,
+}
+
+impl Broken {
+    fn new(name: &str, path: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "f1scraper-keep-going-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        common::fixture_cache(&dir.join("cache"));
+        common::put_page(
+            &ResponseCache::on_disk(dir.join("cache")),
+            path,
+            "<html><body>Not found</body></html>",
+        );
+        Self { dir }
+    }
+
+    fn run(&self, args: &[&str], keep_going: bool) -> Output {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_f1scraper"));
+        cmd.args(["--cache-dir", self.dir.join("cache").to_str().unwrap()])
+            .args(["--no-progress", "--retries", "0", "--timeout", "2"])
+            .args(args)
+            .env("XDG_CONFIG_HOME", &self.dir)
+            .env_remove("F1SCRAPER_KEEP_GOING");
+        if keep_going {
+            cmd.arg("--keep-going");
+        }
+        cmd.output().unwrap()
+    }
+}
+
+impl Drop for Broken {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn failed_pages_are_reported_when_keeping_going() {
+    let race_result = "/2007/races/1/australia/race-result.html";
+    let cases: [(&str, &[&str], &str); 7] = [
+        (race_result, &["simulate"], "race results"),
+        (
+            race_result,
+            &["compare", "teammates", "--no-qualifying"],
+            "race results",
+        ),
+        (race_result, &["driver", "registry"], "season"),
+        (
+            race_result,
+            &[
+                "circuit",
+                "history",
+                "australia",
+                "--venue",
+                "--no-qualifying",
+            ],
+            "race result Australia",
+        ),
+        (
+            "/2007/drivers.html",
+            &["driver", "career", "raikkonen"],
+            "driver summary",
+        ),
+        // names missing from a season
+        (
+            "/2007/fastest-laps.html",
+            &["driver", "result", "verstappen"],
+            "driver selection",
+        ),
+        (
+            "/2007/fastest-laps.html",
+            &["team", "result", "brawn"],
+            "team selection",
+        ),
+    ];
+    for (page, command, target) in cases {
+        let broken = Broken::new(command[0], page);
+        let args = [command, &["--year", "2007"]].concat();
+
+        let output = broken.run(&args, false);
+        assert!(!output.status.success(), "{command:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("Error report:"), "{command:?}: {stderr}");
+
+        let output = broken.run(&args, true);
+        assert!(!output.status.success(), "{command:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(&format!("[2007][{target}] failed: ")),
+            "{command:?}: {stderr}"
+        );
+        assert!(stderr.contains("Error report:"), "{command:?}: {stderr}");
+    }
+}
@@ -0,0 +1,167 @@
+use std::process::Command;
+
+use f1scraper::lineage::Lineage;
+use f1scraper::types::Team;
+
+mod common;
+
+#[test]
+fn resolves_slugs_by_season() {
+    let lineage = Lineage::builtin();
+    let cases = [
+        (1984, "toleman_hart", Some(("enstone", "Toleman"))),
+        (1995, "benetton_renault", Some(("enstone", "Benetton"))),
+        (2005, "renault", Some(("enstone", "Renault"))),
+        (1980, "renault", Some(("renault", "Renault"))),
+        (2013, "lotus_renault", Some(("enstone", "Lotus"))),
+        (2011, "lotus_renault", Some(("caterham", "Lotus"))),
+        (1978, "lotus_ford", Some(("team-lotus", "Lotus"))),
+        (1955, "mercedes", Some(("mercedes", "Mercedes"))),
+        (2014, "mercedes", Some(("brackley", "Mercedes"))),
+        (2024, "kick_sauber_ferrari", Some(("hinwil", "Kick Sauber"))),
+        (2015, "sauber_ferrari", Some(("hinwil", "Sauber"))),
+        (
+            2021,
+            "alfa_romeo_racing_ferrari",
+            Some(("hinwil", "Alfa Romeo")),
+        ),
+        (1951, "alfa_romeo", Some(("alfa-romeo", "Alfa Romeo"))),
+        (
+            2018,
+            "force_india_mercedes",
+            Some(("silverstone", "Force India")),
+        ),
+        (
+            2018,
+            "racing_point_force_india_mercedes",
+            Some(("silverstone", "Racing Point")),
+        ),
+        (2024, "rb_honda_rbpt", Some(("faenza", "RB"))),
+        (
+            2024,
+            "red_bull_racing_honda_rbpt",
+            Some(("milton-keynes", "Red Bull")),
+        ),
+        // a slug only matches a whole prefix
+        (2016, "haasf1", None),
+        (1990, "renault", None),
+        (2007, "super_aguri_honda", None),
+    ];
+    for (year, slug, expected) in cases {
+        let found = lineage
+            .resolve_slug(year, slug)
+            .map(|(constructor, era)| (constructor.id.as_str(), era.name.as_str()));
+        assert_eq!(found, expected, "{year} {slug}");
+    }
+
+    let team = |name: &str| Team {
+        name: name.to_string(),
+        display_name: String::new(),
+    };
+    assert_eq!(
+        lineage.constructor_id(1986, &team("benetton_bmw")),
+        "enstone"
+    );
+    assert_eq!(
+        lineage.constructor_id(2007, &team("super_aguri_honda")),
+        "super_aguri_honda"
+    );
+}
+
+#[test]
+fn finds_constructors_by_name() {
+    let lineage = Lineage::builtin();
+    assert_eq!(lineage.find("Benetton").unwrap().id, "enstone");
+    assert_eq!(lineage.find("toro rosso").unwrap().id, "faenza");
+    assert_eq!(lineage.find("team-lotus").unwrap().id, "team-lotus");
+    let err = lineage.find("lotus").unwrap_err().to_string();
+    assert!(err.contains("ambiguous"), "{err}");
+}
+
+#[test]
+fn rejects_invalid_datasets() {
+    let dataset = |version: u32, eras: &str| {
+        format!(
+            r#"
+version = {version}
+
+[[constructors]]
+id = "enstone"
+name = "Enstone"
+eras = [
+    {{ from = 1986, to = 2001, name = "Benetton", slugs = ["benetton"] }},
+    {{ from = 2002, to = 2011, name = "Renault", slugs = ["renault"] }},
+]
+
+[[constructors]]
+id = "renault"
+name = "Renault"
+eras = [{eras}]
+"#
+        )
+    };
+
+    let valid = r#"{ from = 1977, to = 1985, name = "Renault", slugs = ["renault"] }"#;
+    assert!(Lineage::parse(&dataset(1, valid)).is_ok());
+
+    let err = Lineage::parse(&dataset(2, valid)).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unsupported lineage dataset version: 2"));
+
+    let cases = [
+        (
+            r#"{ from = 1977, to = 2002, name = "Renault", slugs = ["renault"] }"#,
+            "slug `renault` of constructor `enstone` (Renault 2002-2011) is also used by `renault` (Renault 1977-2002)",
+        ),
+        // prefixes of one another
+        (
+            r#"{ from = 2010, name = "Renault", slugs = ["renault_f1"] }"#,
+            "slug `renault` of constructor `enstone`",
+        ),
+        (
+            r#"{ from = 1985, to = 1977, name = "Renault", slugs = ["renault"] }"#,
+            "invalid era of constructor `renault` in lineage dataset: Renault 1985-1977",
+        ),
+        (
+            r#"{ from = 1977, name = "Renault", slugs = [] }"#,
+            "invalid era of constructor `renault`",
+        ),
+    ];
+    for (eras, expected) in cases {
+        let err = Lineage::parse(&dataset(1, eras)).unwrap_err();
+        assert!(err.to_string().contains(expected), "{eras}: {err}");
+    }
+
+    // a season with two names of the same constructor is fine, as long as the
+    // slugs differ
+    let eras = r#"
+    { from = 1977, to = 1985, name = "Renault", slugs = ["renault"] },
+    { from = 1985, to = 1986, name = "Equipe Renault", slugs = ["equipe_renault"] },
+"#;
+    assert!(Lineage::parse(&dataset(1, eras)).is_ok());
+}
+
+#[test]
+fn team_history_aggregates_the_names_of_a_constructor() {
+    let dir = std::env::temp_dir().join(format!("f1scraper-lineage-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    common::fixture_cache(&dir.join("cache"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_f1scraper"))
+        .args(["--cache-dir", dir.join("cache").to_str().unwrap()])
+        .args(["--no-progress", "--retries", "0", "--timeout", "2"])
+        .args(["team", "history", "mclaren", "--year", "2007"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(lines[0].starts_with("[McLaren (mclaren)][2007][McLaren] TeamSummaryEntry {"));
+    assert!(lines[0].contains("mclaren_mercedes"));
+    assert!(lines[1].starts_with("[McLaren (mclaren)][total] pts: "));
+}
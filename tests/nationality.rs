@@ -0,0 +1,61 @@
+use f1scraper::types::Nationality;
+
+#[test]
+fn nationalities_map_to_iso_codes() {
+    let gbr = Nationality::parse("GBR");
+    assert_eq!(gbr.iso.as_deref(), Some("GB"));
+    assert_eq!(gbr.country_name(), Some("United Kingdom"));
+    assert!(!gbr.historical);
+
+    let frg = Nationality::parse("frg");
+    assert_eq!(frg.code, "FRG");
+    assert_eq!(frg.name.as_deref(), Some("West Germany"));
+    assert_eq!(frg.country_name(), Some("Germany"));
+    assert!(frg.historical);
+
+    let rho = Nationality::parse("RHO");
+    assert_eq!(rho.iso.as_deref(), Some("RHZW"));
+    assert_eq!(rho.country_name(), Some("Zimbabwe"));
+
+    let urs = Nationality::parse("URS");
+    assert_eq!(urs.iso.as_deref(), Some("SUHH"));
+    assert_eq!(urs.country.as_deref(), Some("RU"));
+    assert_eq!(urs.country_name(), Some("Russia"));
+
+    let raf = Nationality::parse("RAF");
+    assert_eq!(raf.country_name(), Some("Russia"));
+
+    let unknown = Nationality::parse("XYZ");
+    assert!(!unknown.is_known());
+    assert_eq!(unknown.to_string(), "XYZ");
+}
+
+#[test]
+fn codes_are_the_ioc_ones_of_the_archive() {
+    let cases = [
+        ("NED", "NL", "Netherlands"),
+        ("SUI", "CH", "Switzerland"),
+        ("MAS", "MY", "Malaysia"),
+        ("URU", "UY", "Uruguay"),
+        ("INA", "ID", "Indonesia"),
+        ("RSA", "ZA", "South Africa"),
+        ("POR", "PT", "Portugal"),
+        ("DEN", "DK", "Denmark"),
+        ("MON", "MC", "Monaco"),
+        ("CHI", "CL", "Chile"),
+        ("GER", "DE", "Germany"),
+        ("THA", "TH", "Thailand"),
+    ];
+    for (code, iso, name) in cases {
+        let nationality = Nationality::parse(code);
+        assert_eq!(nationality.iso.as_deref(), Some(iso), "{code}");
+        assert_eq!(nationality.country_name(), Some(name), "{code}");
+    }
+
+    // ISO 3166-1 alpha-3 codes differing from the IOC ones are not used
+    for code in [
+        "IDN", "NLD", "CHE", "MYS", "URY", "ZAF", "PRT", "DNK", "MCO", "CHL", "DEU",
+    ] {
+        assert!(!Nationality::parse(code).is_known(), "{code}");
+    }
+}
@@ -0,0 +1,214 @@
+use chrono::NaiveDate;
+use f1scraper::schema::Schemas;
+use f1scraper::types::{Circuit, DriverName, DriverSummary, RaceResult, RaceSummary, TeamSummary};
+
+fn page(wrapper: &str, table: &str) -> String {
+    format!("<html><body>{wrapper}<table class=\"resultsarchive-table\">{table}</table></div></div></div></body></html>")
+}
+
+fn race_result(rows: &str) -> RaceResult {
+    let html = page(
+        "<div class=\"resultsarchive-wrapper\"><div class=\"resultsarchive-content\"><div class=\"resultsarchive-col-right\">",
+        &format!(
+            "<thead><tr><th class=\"limiter\"></th><th>Pos</th><th>No</th><th>Driver</th><th>Car</th><th>Laps</th><th>Time/Retired</th><th>PTS</th><th class=\"limiter\"></th></tr></thead><tbody>{rows}</tbody>"
+        ),
+    );
+    let circuit = Circuit {
+        idx: 1141,
+        name: "bahrain".to_string(),
+        display_name: "Bahrain".to_string(),
+    };
+    RaceResult::parse(&html, 2023, &circuit).unwrap()
+}
+
+#[test]
+fn driver_cell_joins_visible_spans() {
+    let result = race_result(
+        r#"<tr><td class="limiter"></td><td class="dark">1</td><td class="dark hide-for-mobile">1</td>
+        <td class="dark bold"><span class="hide-for-tablet">Max</span> <span class="hide-for-mobile">Verstappen</span> <span class="uppercase hide-for-desktop">VER</span></td>
+        <td class="semi-bold uppercase hide-for-tablet">Red Bull Racing Honda RBPT</td><td class="bold hide-for-mobile">57</td><td class="dark bold">1:33:56.736</td><td class="bold">25</td><td class="limiter"></td></tr>"#,
+    );
+
+    assert_eq!(result.data.len(), 1);
+    assert_eq!(result.data[0].driver, "Max Verstappen");
+    assert_eq!(result.data[0].car, "Red Bull Racing Honda RBPT");
+    assert_eq!(result.data[0].time_retired, "1:33:56.736");
+}
+
+#[test]
+fn driver_cell_decodes_accented_names() {
+    let result = race_result(
+        r#"<tr><td class="limiter"></td><td>2</td><td>11</td><td><span class="hide-for-tablet">Sergio</span> <span class="hide-for-mobile">P&eacute;rez</span> <span class="uppercase hide-for-desktop">PER</span></td><td>Red Bull Racing Honda RBPT</td><td>57</td><td>+11.987s</td><td>18</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td>3</td><td>7</td><td><span class="hide-for-tablet">Kimi</span> <span class="hide-for-mobile">R&auml;ikk&#246;nen</span> <span class="uppercase hide-for-desktop">RAI</span></td><td>Alfa Romeo Ferrari</td><td>57</td><td>+20.123s</td><td>15</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td>4</td><td>27</td><td><span class="hide-for-tablet">Nico</span> <span class="hide-for-mobile">H&uuml;lkenberg</span> <span class="uppercase hide-for-desktop">HUL</span></td><td>Haas Ferrari</td><td>57</td><td>+30.456s</td><td>12</td><td class="limiter"></td></tr>"#,
+    );
+
+    let drivers: Vec<_> = result.data.iter().map(|e| e.driver.as_str()).collect();
+    assert_eq!(
+        drivers,
+        ["Sergio Pérez", "Kimi Räikkönen", "Nico Hülkenberg"]
+    );
+}
+
+#[test]
+fn driver_cell_splits_name_and_code() {
+    let result = race_result(
+        r#"<tr><td class="limiter"></td><td>1</td><td>1</td><td><span class="hide-for-tablet">Max</span> <span class="hide-for-mobile">Verstappen</span> <span class="uppercase hide-for-desktop">ver</span></td><td>Red Bull Racing Honda RBPT</td><td>57</td><td>1:33:56.736</td><td>25</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td>2</td><td>2</td><td><span class="hide-for-tablet">Nino</span> <span class="hide-for-mobile">Farina</span> <span class="uppercase hide-for-desktop"></span></td><td>Alfa Romeo</td><td>70</td><td>2:13:23.600</td><td>9</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td>3</td><td>10</td><td>Juan Manuel Fangio</td><td>Alfa Romeo</td><td>70</td><td>+2.600s</td><td>6</td><td class="limiter"></td></tr>"#,
+    );
+
+    let names: Vec<_> = result.data.iter().map(|e| e.driver_name.clone()).collect();
+    assert_eq!(
+        names,
+        [
+            DriverName::new("Max", "Verstappen", Some("VER")),
+            DriverName::new("Nino", "Farina", None),
+            DriverName::new("Juan", "Manuel Fangio", None),
+        ]
+    );
+    assert_eq!(result.data[2].driver, "Juan Manuel Fangio");
+}
+
+#[test]
+fn race_result_reads_venue_header() {
+    let html = std::fs::read_to_string("tests/fixtures/race-result.html").unwrap();
+    let result = RaceResult::parse(&html, 2021, &Circuit::default()).unwrap();
+
+    let venue = result.venue.unwrap();
+    assert_eq!(venue.circuit, "Autodromo Enzo e Dino Ferrari");
+    assert_eq!(venue.location, "Imola");
+    assert_eq!(venue.slug(), "autodromo-enzo-e-dino-ferrari");
+    assert_eq!(venue.start_date, NaiveDate::from_ymd_opt(2021, 4, 16));
+    assert_eq!(venue.end_date, NaiveDate::from_ymd_opt(2021, 4, 18));
+    assert_eq!(result.data.len(), 1);
+
+    // the weekend started the month before the race
+    let html = html
+        .replace(">16<", ">30<")
+        .replace("18 Apr 2021", "01 May 2021");
+    let venue = RaceResult::parse(&html, 2021, &Circuit::default())
+        .unwrap()
+        .venue
+        .unwrap();
+    assert_eq!(venue.start_date, NaiveDate::from_ymd_opt(2021, 4, 30));
+
+    let result = race_result("");
+    assert!(result.venue.is_none());
+
+    // layouts without a header selector don't read the venue
+    let mut schemas = Schemas::builtin();
+    schemas.pages.iter_mut().for_each(|page| page.header = None);
+    let result = RaceResult::parse_with(&html, 2021, &Circuit::default(), &schemas).unwrap();
+    assert!(result.venue.is_none());
+    assert_eq!(result.data.len(), 1);
+}
+
+#[test]
+fn cells_collapse_whitespace_and_strip_markup() {
+    let result = race_result(
+        "<tr><td class=\"limiter\"></td><td>\n  NC\n</td><td>16</td><td><span>Charles</span>\n\n<span>Leclerc</span></td><td><strong>Ferrari</strong></td><td>39</td><td>\tDNF&nbsp;</td><td>0</td><td class=\"limiter\"></td></tr>",
+    );
+
+    let entry = &result.data[0];
+    assert_eq!(entry.pos, "NC");
+    assert_eq!(entry.driver, "Charles Leclerc");
+    assert_eq!(entry.car, "Ferrari");
+    assert_eq!(entry.time_retired, "DNF");
+    assert!(entry.is_dnf());
+}
+
+#[test]
+fn summary_links_decode_entities() {
+    let html = page(
+        "<div><div class=\"resultsarchive-content\"><div class=\"table-wrap\">",
+        r#"<thead><tr><th class="limiter"></th><th>Grand Prix</th><th>Date</th><th>Winner</th><th>Car</th><th>Laps</th><th>Time</th><th class="limiter"></th></tr></thead><tbody>
+        <tr><td class="limiter"></td><td><a href="/en/results.html/1985/races/433/monaco/race-result.html">Monaco</a></td><td>19 May 1985</td><td><span class="hide-for-tablet">Alain</span> <span class="hide-for-mobile">Prost</span> <span class="uppercase hide-for-desktop">PRO</span></td><td>McLaren TAG</td><td>78</td><td>1:51:58.034</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td><a href="/en/results.html/1985/races/434/canada/race-result.html">
+            Canada &amp; Montr&eacute;al
+        </a></td><td>16 Jun 1985</td><td><span class="hide-for-tablet">Michele</span> <span class="hide-for-mobile">Alboreto</span> <span class="uppercase hide-for-desktop">ALB</span></td><td>Ferrari</td><td>70</td><td>1:46:01.813</td><td class="limiter"></td></tr>
+        </tbody>"#,
+    );
+
+    let summary = RaceSummary::parse(&html, 1985).unwrap();
+    assert_eq!(summary.data[1].grand_prix, "Canada & Montréal");
+    assert_eq!(summary.data[1].winner, "Michele Alboreto");
+
+    let circuit = summary.data[1].circuit().unwrap();
+    assert_eq!(circuit.idx, 434);
+    assert_eq!(circuit.display_name, "Canada & Montréal");
+}
+
+#[test]
+fn driver_summary_decodes_linked_spans() {
+    let html = page(
+        "<div class=\"resultsarchive-wrapper\"><div class=\"resultsarchive-content\"><div class=\"table-wrap\">",
+        r#"<thead><tr><th class="limiter"></th><th>Pos</th><th>Driver</th><th>Nationality</th><th>Car</th><th>PTS</th><th class="limiter"></th></tr></thead><tbody>
+        <tr><td class="limiter"></td><td>1</td><td><a href="/en/results.html/2007/drivers/KIMRAI01/kimi-raikkonen.html"><span class="hide-for-tablet">Kimi</span> <span class="hide-for-mobile">R&auml;ikk&ouml;nen</span> <span class="uppercase hide-for-desktop">RAI</span></a></td><td>FIN</td><td><a href="/en/results.html/2007/team/ferrari.html">Ferrari</a></td><td>110</td><td class="limiter"></td></tr>
+        </tbody>"#,
+    );
+
+    let summary = DriverSummary::parse(&html, 2007).unwrap();
+    let entry = &summary.data[0];
+    assert_eq!(entry.driver, "Kimi Räikkönen");
+    assert_eq!(entry.car, "Ferrari");
+
+    let driver = entry.driver().unwrap();
+    assert_eq!(driver.id, "KIMRAI01");
+    assert_eq!(driver.display_name, "Kimi Räikkönen");
+}
+
+#[test]
+fn team_summary_decodes_ampersands() {
+    let html = page(
+        "<div><div class=\"resultsarchive-content\"><div class=\"table-wrap\">",
+        r#"<thead><tr><th class="limiter"></th><th>Pos</th><th>Team</th><th>PTS</th><th class="limiter"></th></tr></thead><tbody>
+        <tr><td class="limiter"></td><td>1</td><td><a href="/en/results.html/1958/team/vanwall.html">Vanwall &amp; Co</a></td><td>48</td><td class="limiter"></td></tr>
+        </tbody>"#,
+    );
+
+    let summary = TeamSummary::parse(&html, 1958).unwrap();
+    assert_eq!(summary.data[0].team, "Vanwall & Co");
+    assert_eq!(summary.data[0].team().unwrap().name, "vanwall");
+}
+
+#[test]
+fn table_report_flags_missing_headers_and_bad_rows() {
+    let wrapper = "<div class=\"resultsarchive-wrapper\"><div class=\"resultsarchive-content\"><div class=\"resultsarchive-col-right\">";
+    let row = "<tr><td class=\"limiter\"></td><td>1</td><td>1</td><td><span class=\"hide-for-tablet\">Max</span> <span class=\"hide-for-mobile\">Verstappen</span> <span class=\"uppercase hide-for-desktop\">VER</span></td><td>Red Bull Racing Honda RBPT</td><td>57</td><td>1:33:56.736</td><td>25</td><td class=\"limiter\"></td></tr>";
+    let headers = |laps: &str| {
+        format!("<thead><tr><th class=\"limiter\"></th><th>Pos</th><th>No</th><th>Driver</th><th>Car</th><th>{laps}</th><th>Time/Retired</th><th>PTS</th><th class=\"limiter\"></th></tr></thead>")
+    };
+    let schemas = Schemas::builtin();
+
+    // a truncated row next to a valid one
+    let html = page(
+        wrapper,
+        &format!(
+            "{}<tbody>{row}<tr><td class=\"limiter\"></td><td>2</td></tr></tbody>",
+            headers("Laps")
+        ),
+    );
+    let report = RaceResult::check(&html, 2023, &schemas);
+    assert!(report.found);
+    assert!(report.missing_headers.is_empty());
+    assert_eq!((report.rows, report.parsed_rows), (2, 1));
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0], "row 1: invalid column count");
+    assert_eq!(report.parse_rate(), 0.5);
+
+    // a renamed column
+    let html = page(
+        wrapper,
+        &format!("{}<tbody>{row}</tbody>", headers("Tours")),
+    );
+    let report = RaceResult::check(&html, 2023, &schemas);
+    assert_eq!(report.missing_headers, ["laps"]);
+    assert_eq!(report.parsed_rows, 0);
+    assert!(!report.is_healthy(1.0));
+
+    // no results table at all
+    let report = RaceResult::check("<html></html>", 2023, &schemas);
+    assert!(!report.found);
+    assert_eq!(report.errors.len(), 1);
+}
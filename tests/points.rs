@@ -0,0 +1,327 @@
+use f1scraper::points::{
+    ConstructorScoring, CountedResults, FastestLapBonus, PointsSystem, Standings,
+};
+use f1scraper::types::{
+    Circuit, FastestLapSummary, FastestLapSummaryEntry, RaceResult, RaceResultEntry,
+};
+
+// Races of a season where each driver finished at the given positions, `0` for a
+// retirement
+fn season(year: u16, drivers: &[(&str, &str, &[u32])]) -> Vec<RaceResult> {
+    let rounds = drivers.iter().map(|(_, _, pos)| pos.len()).max().unwrap();
+    (0..rounds)
+        .map(|round| {
+            let data = drivers
+                .iter()
+                .map(|(driver, car, pos)| RaceResultEntry {
+                    pos: match pos[round] {
+                        0 => "NC".to_string(),
+                        pos => pos.to_string(),
+                    },
+                    driver: driver.to_string(),
+                    car: car.to_string(),
+                    ..Default::default()
+                })
+                .collect();
+            race(year, &format!("race-{}", round + 1), data)
+        })
+        .collect()
+}
+
+fn race(year: u16, name: &str, data: Vec<RaceResultEntry>) -> RaceResult {
+    RaceResult {
+        year,
+        circuit: Circuit {
+            idx: 1,
+            name: name.to_string(),
+            display_name: name.to_string(),
+        },
+        venue: None,
+        data,
+    }
+}
+
+fn entry(driver: &str, pos: u32) -> RaceResultEntry {
+    RaceResultEntry {
+        pos: pos.to_string(),
+        driver: driver.to_string(),
+        car: format!("{driver} Racing"),
+        ..Default::default()
+    }
+}
+
+fn simulate(system: u16, races: &[RaceResult]) -> Standings {
+    let system = PointsSystem::for_year(system).unwrap();
+    Standings::simulate(&system, races, None).unwrap()
+}
+
+// (name, pts, dropped pts) of the drivers in standings order
+fn drivers(standings: &Standings) -> Vec<(&str, f32, f32)> {
+    standings
+        .drivers
+        .iter()
+        .map(|row| (row.name.as_str(), row.pts, row.dropped_pts))
+        .collect()
+}
+
+#[test]
+fn scoring_rules_of_each_era() {
+    let cases = [
+        (
+            1950,
+            8.0,
+            5,
+            Some(FastestLapBonus::Always(1.0)),
+            CountedResults::Best(4),
+        ),
+        (1960, 8.0, 6, None, CountedResults::Best(6)),
+        (1964, 9.0, 6, None, CountedResults::Best(6)),
+        (
+            1967,
+            9.0,
+            6,
+            None,
+            CountedResults::Split {
+                races: 6,
+                first: 5,
+                second: 4,
+            },
+        ),
+        (1988, 9.0, 6, None, CountedResults::Best(11)),
+        (1991, 10.0, 6, None, CountedResults::All),
+        (2003, 10.0, 8, None, CountedResults::All),
+        (2010, 25.0, 10, None, CountedResults::All),
+        (
+            2019,
+            25.0,
+            10,
+            Some(FastestLapBonus::InTop(1.0, 10)),
+            CountedResults::All,
+        ),
+        (2025, 25.0, 10, None, CountedResults::All),
+    ];
+    for (year, win, scoring, fastest_lap, counted) in cases {
+        let system = PointsSystem::for_year(year).unwrap();
+        assert_eq!(system.positions[0], win, "{year}");
+        assert_eq!(system.positions.len(), scoring, "{year}");
+        assert_eq!(system.fastest_lap, fastest_lap, "{year}");
+        assert_eq!(system.counted, counted, "{year}");
+    }
+
+    assert_eq!(
+        PointsSystem::for_year(1978).unwrap().constructors,
+        ConstructorScoring::BestCarOnly
+    );
+    assert_eq!(
+        PointsSystem::for_year(1979).unwrap().constructors,
+        ConstructorScoring::AllCars
+    );
+    assert!(PointsSystem::for_year(1949).is_err());
+    assert_eq!("2010".parse::<PointsSystem>().unwrap().year, 2010);
+    assert!("F1".parse::<PointsSystem>().is_err());
+}
+
+#[test]
+fn best_eleven_results_of_1988() {
+    #[rustfmt::skip]
+    let races = season(1988, &[
+        ("Alain Prost", "McLaren Honda", &[1, 2, 1, 1, 2, 2, 1, 0, 2, 2, 2, 0, 1, 1, 2, 1]),
+        ("Ayrton Senna", "McLaren Honda", &[0, 1, 0, 2, 1, 1, 2, 1, 1, 1, 1, 10, 6, 4, 1, 2]),
+    ]);
+
+    let standings = simulate(1988, &races);
+    // Prost scored more points but only the best 11 results counted
+    assert_eq!(
+        drivers(&standings),
+        [("Ayrton Senna", 90.0, 4.0), ("Alain Prost", 87.0, 18.0)]
+    );
+    assert_eq!(standings.drivers[0].wins, 8);
+
+    // every result counts under the 1991 system
+    let standings = simulate(1991, &races);
+    assert_eq!(
+        drivers(&standings),
+        [("Alain Prost", 112.0, 0.0), ("Ayrton Senna", 102.0, 0.0)]
+    );
+}
+
+#[test]
+fn best_six_results_of_1964() {
+    #[rustfmt::skip]
+    let races = season(1964, &[
+        ("John Surtees", "Ferrari", &[0, 2, 0, 0, 3, 1, 0, 1, 2, 2]),
+        ("Graham Hill", "BRM", &[1, 4, 5, 2, 2, 2, 0, 0, 1, 11]),
+    ]);
+
+    assert_eq!(
+        drivers(&simulate(1964, &races)),
+        [("John Surtees", 40.0, 0.0), ("Graham Hill", 39.0, 2.0)]
+    );
+}
+
+#[test]
+fn split_season_results() {
+    // 1967: best 5 of the first 6 races, best 4 of the remaining ones
+    #[rustfmt::skip]
+    let races = season(1967, &[
+        ("A", "A Racing", &[1, 1, 1, 1, 1, 1, 6, 6, 6, 6, 6]),
+        ("B", "B Racing", &[2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 2]),
+    ]);
+
+    assert_eq!(
+        drivers(&simulate(1967, &races)),
+        [("B", 66.0, 12.0), ("A", 49.0, 10.0)]
+    );
+}
+
+#[test]
+fn ties_are_broken_on_countback() {
+    let cases: [&[(&str, &str, &[u32])]; 3] = [
+        // a win beats a second and a third place
+        &[("A", "A Racing", &[2, 3]), ("B", "B Racing", &[1, 0])],
+        // same wins, a second place beats a third and a fifth place
+        &[("A", "A Racing", &[1, 3, 5]), ("B", "B Racing", &[1, 2, 0])],
+        // same results, by name
+        &[("B", "B Racing", &[1]), ("A", "A Racing", &[1])],
+    ];
+    let expected = [["B", "A"], ["B", "A"], ["A", "B"]];
+    for (drivers, expected) in cases.into_iter().zip(expected) {
+        let standings = simulate(1991, &season(1991, drivers));
+        let names: Vec<_> = standings
+            .drivers
+            .iter()
+            .map(|row| row.name.as_str())
+            .collect();
+        assert_eq!(names, expected);
+        assert_eq!(standings.drivers[0].pts, standings.drivers[1].pts);
+    }
+}
+
+#[test]
+fn best_car_only_scores_for_constructors() {
+    let races = vec![race(
+        1970,
+        "race-1",
+        vec![
+            RaceResultEntry {
+                car: "Ferrari".to_string(),
+                ..entry("A", 1)
+            },
+            RaceResultEntry {
+                car: "Ferrari".to_string(),
+                ..entry("B", 2)
+            },
+        ],
+    )];
+
+    let constructors = |system| {
+        simulate(system, &races)
+            .constructors
+            .iter()
+            .map(|row| (row.name.clone(), row.pts))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(constructors(1970), [("Ferrari".to_string(), 9.0)]);
+    assert_eq!(constructors(1979), [("Ferrari".to_string(), 15.0)]);
+}
+
+#[test]
+fn no_constructors_standings_before_1958() {
+    let races = |year| vec![race(year, "monaco", vec![entry("A", 1)])];
+    assert!(simulate(1957, &races(1957)).constructors.is_empty());
+    assert_eq!(simulate(1957, &races(1957)).drivers.len(), 1);
+    assert_eq!(simulate(1958, &races(1958)).constructors.len(), 1);
+}
+
+#[test]
+fn half_and_double_points_races() {
+    let races = vec![
+        race(2009, "australia", vec![entry("Jenson Button", 1)]),
+        race(2009, "malaysia", vec![entry("Jenson Button", 1)]),
+    ];
+    assert_eq!(
+        drivers(&simulate(2009, &races)),
+        [("Jenson Button", 15.0, 0.0)]
+    );
+    // the race was stopped whatever the system
+    assert_eq!(
+        drivers(&simulate(2010, &races)),
+        [("Jenson Button", 37.5, 0.0)]
+    );
+
+    // Monaco 1984 decided the title, by half a point
+    let races = vec![
+        race(1984, "monaco", vec![entry("Alain Prost", 1)]),
+        race(1984, "portugal", vec![entry("Niki Lauda", 2)]),
+    ];
+    assert_eq!(
+        drivers(&simulate(1984, &races)),
+        [("Niki Lauda", 6.0, 0.0), ("Alain Prost", 4.5, 0.0)]
+    );
+    let races = vec![
+        race(1975, "spain", vec![entry("Jochen Mass", 1)]),
+        race(1975, "austria", vec![entry("Vittorio Brambilla", 1)]),
+    ];
+    assert_eq!(
+        drivers(&simulate(1975, &races)),
+        [("Jochen Mass", 4.5, 0.0), ("Vittorio Brambilla", 4.5, 0.0)]
+    );
+
+    let races = vec![
+        race(2014, "brazil", vec![entry("Lewis Hamilton", 2)]),
+        race(2014, "abu-dhabi", vec![entry("Lewis Hamilton", 1)]),
+    ];
+    assert_eq!(
+        drivers(&simulate(2014, &races)),
+        [("Lewis Hamilton", 68.0, 0.0)]
+    );
+    // double points were a rule of the 2014 system
+    assert_eq!(
+        drivers(&simulate(2013, &races)),
+        [("Lewis Hamilton", 43.0, 0.0)]
+    );
+}
+
+#[test]
+fn fastest_lap_bonus() {
+    let races = vec![race(2012, "britain", vec![entry("A", 1), entry("B", 11)])];
+    let fastest_laps = |driver: &str| FastestLapSummary {
+        year: 2012,
+        data: vec![FastestLapSummaryEntry {
+            grand_prix: "britain".to_string(),
+            driver: driver.to_string(),
+            ..Default::default()
+        }],
+    };
+    let simulate = |system, driver| {
+        let system = PointsSystem::for_year(system).unwrap();
+        let standings = Standings::simulate(&system, &races, Some(&fastest_laps(driver))).unwrap();
+        drivers(&standings)
+            .into_iter()
+            .map(|(name, pts, _)| (name.to_string(), pts))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        simulate(2019, "A"),
+        [("A".to_string(), 26.0), ("B".to_string(), 0.0)]
+    );
+    // outside of the top 10
+    assert_eq!(
+        simulate(2019, "B"),
+        [("A".to_string(), 25.0), ("B".to_string(), 0.0)]
+    );
+    assert_eq!(
+        simulate(1950, "B"),
+        [("A".to_string(), 8.0), ("B".to_string(), 1.0)]
+    );
+}
+
+#[test]
+fn refuses_sprint_seasons() {
+    let races = vec![race(2021, "bahrain", vec![entry("Lewis Hamilton", 1)])];
+    let system = PointsSystem::for_year(2021).unwrap();
+    let err = Standings::simulate(&system, &races, None).unwrap_err();
+    assert!(err.to_string().contains("sprint results are not scraped"));
+    assert!(Standings::check_season(2020).is_ok());
+}
@@ -0,0 +1,108 @@
+use f1scraper::registry::{DriverRegistry, MentionSource};
+use f1scraper::types::{
+    Circuit, DriverName, DriverSummary, DriverSummaryEntry, FastestLapSummary,
+    FastestLapSummaryEntry, RaceResult, RaceResultEntry,
+};
+
+fn summary_entry(id: &str, slug: &str, name: DriverName) -> DriverSummaryEntry {
+    DriverSummaryEntry {
+        url: format!("/en/results.html/2007/drivers/{id}/{slug}.html"),
+        driver: name.full_name(),
+        driver_name: name,
+        ..Default::default()
+    }
+}
+
+fn registry() -> DriverRegistry {
+    let summary = DriverSummary {
+        year: 2007,
+        data: vec![
+            summary_entry(
+                "KIMRAI01",
+                "kimi-raikkonen",
+                DriverName::new("Kimi", "Räikkönen", Some("RAI")),
+            ),
+            summary_entry(
+                "LEWHAM01",
+                "lewis-hamilton",
+                DriverName::new("Lewis", "Hamilton", Some("HAM")),
+            ),
+            summary_entry(
+                "RALSCH01",
+                "ralf-schumacher",
+                DriverName::new("Ralf", "Schumacher", Some("SCH")),
+            ),
+            summary_entry(
+                "MICSCH01",
+                "michael-schumacher",
+                DriverName::new("Michael", "Schumacher", Some("MSC")),
+            ),
+        ],
+    };
+    DriverRegistry::from_summary(&summary).unwrap()
+}
+
+#[test]
+fn resolves_names_codes_and_unique_last_names() {
+    let registry = registry();
+    let id = |name: DriverName| registry.resolve(&name).map(|d| d.id.clone());
+
+    assert_eq!(
+        id(DriverName::new("Kimi", "Raikkonen", None)).as_deref(),
+        Some("KIMRAI01")
+    );
+    assert_eq!(
+        id(DriverName::new("L.", "Hamilton", Some("ham"))).as_deref(),
+        Some("LEWHAM01")
+    );
+    assert_eq!(
+        id(DriverName::new("", "Hamilton", None)).as_deref(),
+        Some("LEWHAM01")
+    );
+    assert_eq!(
+        id(DriverName::new("R.", "Schumacher", Some("SCH"))).as_deref(),
+        Some("RALSCH01")
+    );
+    // shared last name without a code is ambiguous
+    assert_eq!(id(DriverName::new("", "Schumacher", None)), None);
+}
+
+#[test]
+fn audit_flags_unresolved_mentions() {
+    let registry = registry();
+    let race = RaceResult {
+        year: 2007,
+        circuit: Circuit {
+            idx: 800,
+            name: "australia".to_string(),
+            display_name: "Australia".to_string(),
+        },
+        data: vec![
+            RaceResultEntry {
+                driver_name: DriverName::new("Kimi", "Räikkönen", Some("RAI")),
+                ..Default::default()
+            },
+            RaceResultEntry {
+                driver_name: DriverName::new("Sebastian", "Vettel", Some("VET")),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let fastest_laps = FastestLapSummary {
+        year: 2007,
+        data: vec![FastestLapSummaryEntry {
+            grand_prix: "Australia".to_string(),
+            driver_name: DriverName::new("Kimi", "Räikkönen", None),
+            ..Default::default()
+        }],
+    };
+
+    let report = registry.audit(None, &[race], &[], Some(&fastest_laps));
+    assert_eq!(report.mentions, 3);
+    assert_eq!(report.resolved, 2);
+    assert_eq!(report.unresolved.len(), 1);
+    assert_eq!(report.unresolved[0].source, MentionSource::RaceResult);
+    assert_eq!(report.unresolved[0].grand_prix, "Australia");
+    assert_eq!(report.unresolved[0].name.last, "Vettel");
+}
@@ -0,0 +1,95 @@
+use f1scraper::resolve::Resolver;
+
+fn circuits() -> Resolver<&'static str> {
+    let mut resolver = Resolver::new("grand prix");
+    for (slug, name) in [
+        ("monaco", "Monaco"),
+        ("italy", "Italy"),
+        ("sao-paulo", "São Paulo"),
+        ("saudi-arabia", "Saudi Arabia"),
+        ("great-britain", "Great Britain"),
+    ] {
+        resolver.add(slug, &[name, slug]);
+    }
+    resolver
+}
+
+#[test]
+fn resolves_exact_prefix_and_accents() {
+    let resolver = circuits();
+    assert_eq!(resolver.resolve("MONACO"), Ok(&"monaco"));
+    assert_eq!(resolver.resolve("great britain"), Ok(&"great-britain"));
+    assert_eq!(resolver.resolve("sao paulo"), Ok(&"sao-paulo"));
+    assert_eq!(resolver.resolve("brit"), Ok(&"great-britain"));
+}
+
+#[test]
+fn resolves_typos() {
+    let resolver = circuits();
+    assert_eq!(resolver.resolve("monacco"), Ok(&"monaco"));
+    assert_eq!(resolver.resolve("itlay"), Ok(&"italy"));
+}
+
+#[test]
+fn reports_ambiguous_names_and_suggestions() {
+    let resolver = circuits();
+
+    let err = resolver.resolve("sa").unwrap_err();
+    assert!(err.ambiguous);
+    assert_eq!(err.suggestions, ["São Paulo", "Saudi Arabia"]);
+
+    let err = resolver.resolve("hungary").unwrap_err();
+    assert!(!err.ambiguous);
+    assert_eq!(err.suggestions.len(), 3);
+    assert!(err
+        .to_string()
+        .starts_with("find grand prix with name: `hungary`, did you mean: "));
+}
+
+#[test]
+fn selects_names_and_patterns_in_order() {
+    let resolver = circuits();
+    let slugs = |queries: &[&str]| {
+        resolver.select(queries).map(|selected| {
+            selected
+                .into_iter()
+                .map(|(idx, slug)| (idx, *slug))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(slugs(&[]).unwrap().len(), 5);
+    assert_eq!(
+        slugs(&["britain", "s*"]),
+        Ok(vec![
+            (2, "sao-paulo"),
+            (3, "saudi-arabia"),
+            (4, "great-britain")
+        ])
+    );
+    assert_eq!(
+        slugs(&["?taly", "monaco"]),
+        Ok(vec![(0, "monaco"), (1, "italy")])
+    );
+    assert!(slugs(&["x*"]).is_err());
+}
+
+#[test]
+fn matches_every_entity_of_the_first_rule() {
+    let resolver = circuits();
+    assert_eq!(resolver.matches("Saudi Arabia"), [&"saudi-arabia"]);
+    assert_eq!(resolver.matches("sao"), [&"sao-paulo"]);
+    assert_eq!(resolver.matches("itlay"), [&"italy"]);
+    // no rule picks one of several candidates
+    assert_eq!(resolver.matches("sau"), [&"saudi-arabia"]);
+    assert_eq!(resolver.matches("a"), Vec::<&&str>::new());
+    assert_eq!(resolver.matches("sa"), Vec::<&&str>::new());
+    assert_eq!(resolver.matches("hungary"), Vec::<&&str>::new());
+
+    let mut resolver = Resolver::new("race");
+    resolver.add(1, &["British", "Silverstone"]);
+    resolver.add(2, &["70th Anniversary", "Silverstone"]);
+    resolver.add(3, &["Hungarian", "Hungaroring"]);
+    assert_eq!(resolver.matches("silverstone"), [&1, &2]);
+    assert_eq!(resolver.matches("hung"), [&3]);
+}
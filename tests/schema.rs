@@ -0,0 +1,138 @@
+use f1scraper::schema::{Extract, Headers, PageKind, Schemas};
+
+const TABLE: &str = "div.table-wrap>table.resultsarchive-table";
+
+fn schemas(version: u32, pages: &str) -> String {
+    format!("version = {version}\n{pages}")
+}
+
+fn page(kind: &str, years: &str, table: &str) -> String {
+    format!(
+        r#"
+[[pages]]
+kind = "{kind}"
+{years}
+table = "{table}"
+columns = [
+    {{ field = "pos", header = "pos" }},
+    {{ field = "driver", header = "driver", extract = "driver_name" }},
+]
+"#
+    )
+}
+
+fn error(s: &str) -> String {
+    format!("{:#}", Schemas::parse(s).unwrap_err())
+}
+
+#[test]
+fn parses_a_custom_layout() {
+    let s = schemas(1, &page("race_result", "from = 2020\nto = 2021", TABLE));
+    let schemas = Schemas::parse(&s).unwrap();
+
+    let page = schemas.page(PageKind::RaceResult, 2020).unwrap();
+    assert_eq!(page.table, TABLE);
+    assert_eq!(page.expected_headers(), ["pos", "driver"]);
+    assert_eq!(page.columns[0].extract, Extract::Text);
+    assert_eq!(page.columns[1].extract, Extract::DriverName);
+    assert_eq!(page.header, None);
+}
+
+#[test]
+fn rejects_invalid_schemas() {
+    let err = error(&schemas(2, &page("race_result", "", TABLE)));
+    assert!(
+        err.contains("unsupported page schemas version: 2 (expected 1)"),
+        "{err}"
+    );
+
+    let err = error(&schemas(1, &page("sprint_result", "", TABLE)));
+    assert!(err.contains("parse page schemas"), "{err}");
+    assert!(err.contains("sprint_result"), "{err}");
+
+    let err = error(&schemas(1, &page("race_result", "", "table>>")));
+    assert!(err.contains("page schema: race_result"), "{err}");
+    assert!(err.contains("invalid table selector: `table>>`"), "{err}");
+
+    let header = page("race_result", "header = \"div>>\"", TABLE);
+    let err = error(&schemas(1, &header));
+    assert!(err.contains("invalid header selector: `div>>`"), "{err}");
+
+    let any = r#"
+[[pages]]
+kind = "qualifying_result"
+table = "table"
+columns = [{ field = "time", header = [] }]
+"#;
+    let err = error(&schemas(1, any));
+    assert!(err.contains("page schema: qualifying_result"), "{err}");
+    assert!(err.contains("no header for field `time`"), "{err}");
+
+    let err = error("pages = []");
+    assert!(err.contains("parse page schemas"), "{err}");
+}
+
+#[test]
+fn builtin_layouts_cover_every_season_since_1950() {
+    let schemas = Schemas::builtin();
+    let kinds = [
+        PageKind::RaceSummary,
+        PageKind::RaceResult,
+        PageKind::QualifyingResult,
+        PageKind::DriverSummary,
+        PageKind::DriverResult,
+        PageKind::TeamSummary,
+        PageKind::TeamResult,
+        PageKind::FastestLapSummary,
+    ];
+    for kind in kinds {
+        for year in [1950, 1988, 2024] {
+            assert!(schemas.page(kind, year).is_ok(), "{kind} in {year}");
+        }
+        let err = schemas.page(kind, 1949).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("no page schema for {kind} in 1949")
+        );
+    }
+
+    let race = schemas.page(PageKind::RaceResult, 2021).unwrap();
+    assert_eq!(
+        race.header.as_deref(),
+        Some("div.resultsarchive-content-header")
+    );
+
+    let qualifying = schemas.page(PageKind::QualifyingResult, 2007).unwrap();
+    assert!(matches!(
+        &qualifying.columns[4].header,
+        Headers::Any(headers) if headers == &["time", "q1", "q2", "q3"]
+    ));
+}
+
+#[test]
+fn extended_layouts_take_precedence_within_their_years() {
+    let custom = [
+        page("race_result", "from = 2020\nto = 2021", "table.custom"),
+        page("race_result", "to = 1960", "table.early"),
+    ]
+    .concat();
+    let schemas = Schemas::builtin().extend(Schemas::parse(&schemas(1, &custom)).unwrap());
+    let table = |year| &schemas.page(PageKind::RaceResult, year).unwrap().table;
+
+    assert_eq!(table(2020), "table.custom");
+    assert_eq!(table(2021), "table.custom");
+    assert_eq!(table(1955), "table.early");
+    assert_eq!(table(1960), "table.early");
+
+    let builtin = Schemas::builtin();
+    let builtin = &builtin.page(PageKind::RaceResult, 2007).unwrap().table;
+    assert_eq!(table(1961), builtin);
+    assert_eq!(table(2019), builtin);
+    assert_eq!(table(2022), builtin);
+
+    // open ended custom layouts also cover the seasons the builtin ones don't
+    assert_eq!(table(1949), "table.early");
+    // other page kinds keep the builtin layouts
+    assert!(schemas.page(PageKind::RaceSummary, 2020).is_ok());
+    assert!(schemas.page(PageKind::RaceSummary, 1949).is_err());
+}
@@ -0,0 +1,222 @@
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
+
+use f1scraper::schema::Schemas;
+use f1scraper::scrape::{
+    PageEvent, RaceResultSummaryTarget, RaceResultTarget, ResponseCache, ScrapeTarget, Scraper,
+    ScraperConfig, Stage, TargetFailure,
+};
+use f1scraper::types::RaceSummary;
+
+// A page outside of the results archive, parsed as its title
+struct TitleTarget(reqwest::Url);
+
+impl ScrapeTarget for TitleTarget {
+    type Output = String;
+
+    fn request(&self) -> reqwest::blocking::Request {
+        reqwest::blocking::Request::new(reqwest::Method::GET, self.0.clone())
+    }
+
+    fn parse(&self, html: &str, _schemas: &Schemas) -> anyhow::Result<String> {
+        let (_, rest) = html.split_once("<title>").context("find title")?;
+        let (title, _) = rest.split_once("</title>").context("find title end")?;
+        Ok(title.to_string())
+    }
+}
+
+fn cached_scraper(url: &reqwest::Url, html: &str) -> Scraper {
+    let cache = ResponseCache::in_memory();
+    cache.put(url, html).unwrap();
+    Scraper::new(reqwest::blocking::Client::new()).with_cache(cache)
+}
+
+#[test]
+fn parse_failures_carry_the_stage_and_url() {
+    let target = TitleTarget(reqwest::Url::parse("https://example.com/").unwrap());
+    let url = target.request().url().clone();
+    let scraper = cached_scraper(&url, "<html></html>");
+
+    let err = scraper.fetch(target).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<TargetFailure>(),
+        Some(&TargetFailure {
+            stage: Stage::Parse,
+            url: url.to_string(),
+        })
+    );
+    assert_eq!(err.root_cause().to_string(), "find title");
+}
+
+#[test]
+fn counts_cache_hits_and_parse_failures() {
+    let target = || RaceResultSummaryTarget::new(2023).unwrap();
+    let scraper = cached_scraper(target().request().url(), "<html></html>");
+
+    scraper.scrape(target()).unwrap();
+    // no results table
+    assert!(scraper.fetch(target()).is_err());
+
+    let stats = scraper.stats();
+    assert_eq!(stats.cache_hits, 2);
+    assert_eq!(stats.parse_failures, 1);
+    assert_eq!(
+        stats.to_string(),
+        "0 pages fetched (0 B), 2 cache hits, 0 retries, 1 parse failure"
+    );
+}
+
+#[test]
+fn iterates_results_page_by_page() {
+    let summary = r#"<html><body><div><div class="resultsarchive-content"><div class="table-wrap"><table class="resultsarchive-table">
+        <thead><tr><th class="limiter"></th><th>Grand Prix</th><th>Date</th><th>Winner</th><th>Car</th><th>Laps</th><th>Time</th><th class="limiter"></th></tr></thead><tbody>
+        <tr><td class="limiter"></td><td><a href="/en/results.html/2023/races/1141/bahrain/race-result.html">Bahrain</a></td><td>05 Mar 2023</td><td>Max Verstappen</td><td>Red Bull Racing Honda RBPT</td><td>57</td><td>1:33:56.736</td><td class="limiter"></td></tr>
+        <tr><td class="limiter"></td><td><a href="/en/results.html/2023/races/1142/saudi-arabia/race-result.html">Saudi Arabia</a></td><td>19 Mar 2023</td><td>Sergio Perez</td><td>Red Bull Racing Honda RBPT</td><td>50</td><td>1:21:14.894</td><td class="limiter"></td></tr>
+        </tbody></table></div></div></div></body></html>"#;
+    let circuits = RaceSummary::parse(summary, 2023)
+        .and_then(|summary| summary.circuits())
+        .unwrap();
+    let url = |idx: usize| {
+        let circuit = circuits.items().nth(idx).unwrap();
+        let target = RaceResultTarget::new(2023, circuit).unwrap();
+        target.request().url().clone()
+    };
+    let summary_url = RaceResultSummaryTarget::new(2023)
+        .unwrap()
+        .request()
+        .url()
+        .clone();
+    let cache = ResponseCache::in_memory();
+    cache.put(&summary_url, summary).unwrap();
+    let race_result = std::fs::read_to_string("tests/fixtures/race-result.html").unwrap();
+    cache.put(&url(0), &race_result).unwrap();
+    // no results table
+    cache.put(&url(1), "<html></html>").unwrap();
+    let scraper = Scraper::new(reqwest::blocking::Client::new()).with_cache(cache);
+
+    let mut listed = 0;
+    let mut results = scraper.results_between(2023, 2023).with_observer(|event| {
+        if let PageEvent::Listed { count, .. } = event {
+            listed += count;
+        }
+    });
+
+    let (year, circuit, entry) = results.next().unwrap().unwrap();
+    assert_eq!((year, circuit.name.as_str()), (2023, "bahrain"));
+    assert_eq!(entry.driver, "Max Verstappen");
+    // the failed page is reported and the iteration ends after it
+    let err = results.next().unwrap().unwrap_err();
+    assert_eq!(
+        (err.year, err.target.as_str()),
+        (2023, "race result Saudi Arabia")
+    );
+    assert!(results.next().is_none());
+    drop(results);
+    assert_eq!(listed, 2);
+}
+
+#[test]
+fn sends_the_configured_user_agent_and_headers() {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let handle = thread::spawn(move || {
+        let request = server.recv().unwrap();
+        let header = |name: &str| {
+            request
+                .headers()
+                .iter()
+                .find(|h| h.field.to_string().eq_ignore_ascii_case(name))
+                .map(|h| h.value.to_string())
+        };
+        let headers = (header("User-Agent"), header("X-Requested-By"));
+        let html = "<html><head><title>Standings</title></head></html>";
+        request
+            .respond(tiny_http::Response::from_string(html))
+            .unwrap();
+        headers
+    });
+
+    let scraper = ScraperConfig::new()
+        .with_user_agent("stats-team/1.0")
+        .with_header("X-Requested-By", "stats-team")
+        .build()
+        .unwrap();
+    let url = reqwest::Url::parse(&format!("http://{addr}/races.html")).unwrap();
+    assert_eq!(scraper.fetch(TitleTarget(url)).unwrap(), "Standings");
+
+    let (user_agent, requested_by) = handle.join().unwrap();
+    assert_eq!(user_agent.as_deref(), Some("stats-team/1.0"));
+    assert_eq!(requested_by.as_deref(), Some("stats-team"));
+}
+
+#[test]
+fn rejects_invalid_client_settings() {
+    assert!(ScraperConfig::new()
+        .with_proxy("not a url")
+        .build()
+        .is_err());
+    assert!(ScraperConfig::new()
+        .with_header("Bad Header", "value")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn refetches_cached_pages_older_than_the_max_age() {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let handle = thread::spawn(move || {
+        for version in 1..=2 {
+            let request = server.recv().unwrap();
+            let html = format!("<html><head><title>v{version}</title></head></html>");
+            request
+                .respond(tiny_http::Response::from_string(html))
+                .unwrap();
+        }
+    });
+
+    let scraper =
+        Scraper::new(reqwest::blocking::Client::new()).with_cache(ResponseCache::in_memory());
+    let url = reqwest::Url::parse(&format!("http://{addr}/races.html")).unwrap();
+    let hour = Duration::from_secs(3600);
+    assert_eq!(scraper.fetch(TitleTarget(url.clone())).unwrap(), "v1");
+    assert_eq!(
+        scraper
+            .fetch_within(TitleTarget(url.clone()), hour)
+            .unwrap(),
+        "v1"
+    );
+    assert_eq!(
+        scraper
+            .fetch_within(TitleTarget(url.clone()), Duration::ZERO)
+            .unwrap(),
+        "v2"
+    );
+    // the cache holds the new page, the server only answers twice
+    assert_eq!(scraper.fetch(TitleTarget(url)).unwrap(), "v2");
+    handle.join().unwrap();
+}
+
+#[test]
+fn disk_cache_ages_pages_by_modification_time() {
+    let dir = std::env::temp_dir().join(format!("f1scraper-cache-age-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let cache = ResponseCache::on_disk(&dir);
+    let url = reqwest::Url::parse("https://example.com/races.html").unwrap();
+    cache.put(&url, "<html></html>").unwrap();
+
+    let hour = Duration::from_secs(3600);
+    assert!(cache.get_within(&url, hour).unwrap().is_some());
+
+    let path = dir.join("example.com/races.html/index.html");
+    std::fs::File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::now() - 2 * hour))
+        .unwrap();
+    assert!(cache.get_within(&url, hour).unwrap().is_none());
+    assert!(cache.get(&url).unwrap().is_some());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,84 @@
+use std::process::Command;
+
+use f1scraper::season::{SeasonNotAvailable, Seasons};
+
+mod common;
+
+const SELECTOR: &str = r#"
+<ul class="resultsarchive-filter ResultFilterScrollable">
+  <li class="resultsarchive-filter-item">
+    <a href="/en/results.html/2024/races.html" data-name="year" data-value="2024"
+       class="resultsarchive-filter-item-link FilterTrigger selected">
+      <span class="clip">2024</span></a>
+  </li>
+  <li class="resultsarchive-filter-item">
+    <a href="/en/results.html/2023/races.html" data-name="year" data-value="2023"
+       class="resultsarchive-filter-item-link FilterTrigger ">
+      <span class="clip">2023</span></a>
+  </li>
+  <li class="resultsarchive-filter-item">
+    <a href="/en/results.html/1950/races.html" data-name="year" data-value="1950"
+       class="resultsarchive-filter-item-link FilterTrigger ">
+      <span class="clip">1950</span></a>
+  </li>
+  <li class="resultsarchive-filter-item">
+    <a href="/en/results.html/2024/races.html" data-name="type" data-value="races"
+       class="resultsarchive-filter-item-link FilterTrigger selected">
+      <span class="clip">Races</span></a>
+  </li>
+</ul>
+"#;
+
+#[test]
+fn parses_season_selector() {
+    let seasons = Seasons::parse(SELECTOR).unwrap();
+    assert_eq!(
+        seasons,
+        Seasons {
+            first: 1950,
+            latest: 2024
+        }
+    );
+    assert!(Seasons::parse("<ul></ul>").is_err());
+}
+
+#[test]
+fn rejects_unavailable_seasons() {
+    let seasons = Seasons::parse(SELECTOR).unwrap();
+    assert_eq!(seasons.check(2024), Ok(2024));
+    assert_eq!(
+        seasons.check(2025),
+        Err(SeasonNotAvailable {
+            year: 2025,
+            seasons
+        })
+    );
+
+    let err = anyhow::Error::new(seasons.check(1949).unwrap_err());
+    assert_eq!(
+        err.to_string(),
+        "season 1949 not available: the archive covers 1950 to 2024"
+    );
+    assert!(err.downcast_ref::<SeasonNotAvailable>().is_some());
+}
+
+#[test]
+fn reuses_the_cached_season_list() {
+    let dir = std::env::temp_dir().join(format!("f1scraper-seasons-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    common::fixture_cache(&dir);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_f1scraper"))
+        .args(["-v", "--cache-dir", dir.to_str().unwrap()])
+        .args(["--no-progress", "--retries", "0", "--timeout", "2"])
+        .args(["race", "result", "--year", "2007"])
+        .env("XDG_CONFIG_HOME", &dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(output.status.success(), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // every page of the run, the archive landing page included, is cached
+    assert!(!stderr.contains("Executing reqwest"), "{stderr}");
+}
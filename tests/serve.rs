@@ -0,0 +1,259 @@
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use f1scraper::scrape::ResponseCache;
+use serde_json::Value;
+
+mod common;
+
+// `f1scraper serve` over a cache holding the fixture pages of the 2007 season
+struct Server {
+    child: Child,
+    addr: String,
+    dir: PathBuf,
+}
+
+impl Server {
+    fn start(name: &str) -> Self {
+        Self::start_with(name, &[])
+    }
+
+    // Replace some of the fixture pages, given as (path, html)
+    fn start_with(name: &str, pages: &[(&str, &str)]) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("f1scraper-serve-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        common::fixture_cache(&dir.join("cache"));
+        let cache = ResponseCache::on_disk(dir.join("cache"));
+        for (path, html) in pages {
+            common::put_page(&cache, path, html);
+        }
+
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .unwrap()
+            .port();
+        let addr = format!("127.0.0.1:{port}");
+        let child = Command::new(env!("CARGO_BIN_EXE_f1scraper"))
+            .args(["--cache-dir", dir.join("cache").to_str().unwrap()])
+            .args(["--no-progress", "--retries", "0", "--timeout", "2"])
+            .args(["serve", "--addr", &addr, "--year", "2007"])
+            // keep the user's config file out of the way
+            .env("XDG_CONFIG_HOME", &dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let server = Self { child, addr, dir };
+        let started = Instant::now();
+        while TcpStream::connect(&server.addr).is_err() {
+            assert!(
+                started.elapsed() < Duration::from_secs(30),
+                "server did not start"
+            );
+            thread::sleep(Duration::from_millis(50));
+        }
+        server
+    }
+
+    fn get(&self, path: &str) -> (u16, String) {
+        let response = reqwest::blocking::get(format!("http://{}{path}", self.addr)).unwrap();
+        (response.status().as_u16(), response.text().unwrap())
+    }
+
+    fn json(&self, path: &str) -> Value {
+        let (status, body) = self.get(path);
+        assert_eq!(status, 200, "{path}: {body}");
+        serde_json::from_str(&body).unwrap()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn ergast_ids_match_across_endpoints() {
+    let server = Server::start("ids");
+
+    let results = server.json("/api/f1/2007/1/results.json");
+    let race = &results["MRData"]["RaceTable"]["Races"][0];
+    let winner = &race["Results"][0];
+    assert_eq!(winner["Driver"]["driverId"], "kimi_raikkonen");
+    assert_eq!(winner["Constructor"]["constructorId"], "ferrari");
+    assert_eq!(
+        race["Results"][1]["Constructor"]["constructorId"],
+        "mclaren_mercedes"
+    );
+    assert_eq!(race["Results"][2]["status"], "Retired");
+    // the venue of the result page
+    assert_eq!(
+        race["Circuit"]["circuitName"],
+        "Albert Park Grand Prix Circuit"
+    );
+    assert_eq!(race["Circuit"]["Location"]["locality"], "Melbourne");
+    assert!(race["Circuit"]["Location"].get("country").is_none());
+
+    let standings = server.json("/api/f1/2007/driverStandings.json");
+    let leader = &standings["MRData"]["StandingsTable"]["StandingsLists"][0]["DriverStandings"][0];
+    assert_eq!(leader["Driver"]["driverId"], "kimi_raikkonen");
+    assert_eq!(leader["wins"], "1");
+    assert_eq!(leader["Constructors"][0]["constructorId"], "ferrari");
+
+    let standings = server.json("/api/f1/2007/constructorStandings.json");
+    let list = &standings["MRData"]["StandingsTable"]["StandingsLists"][0];
+    assert_eq!(
+        list["ConstructorStandings"][1]["Constructor"]["constructorId"],
+        "mclaren_mercedes"
+    );
+
+    let fastest = server.json("/api/f1/2007/fastest/1/results.json");
+    let lap = &fastest["MRData"]["RaceTable"]["Races"][0]["Results"][0];
+    assert_eq!(lap["Driver"]["driverId"], "kimi_raikkonen");
+    assert_eq!(lap["FastestLap"]["Time"]["time"], "1:25.235");
+
+    // races don't scrape the result pages, the location is unknown
+    let races = server.json("/api/f1/current/races.json");
+    let race = &races["MRData"]["RaceTable"]["Races"][0];
+    assert_eq!(race["date"], "2007-03-18");
+    assert!(race["Circuit"].get("Location").is_none());
+}
+
+#[test]
+fn ergast_matches_races_and_drivers_across_pages() {
+    // the winner is spelled without accents nor code, and the fastest laps are
+    // listed out of the calendar order
+    let races = std::fs::read_to_string("tests/fixtures/2007/races.html")
+        .unwrap()
+        .replace(
+            r#"<td><span class="hide-for-tablet">Kimi</span> <span class="hide-for-mobile">R&auml;ikk&ouml;nen</span> <span class="uppercase hide-for-desktop">RAI</span></td>"#,
+            "<td>Kimi Raikkonen</td>",
+        )
+        .replace(
+            "</tbody>",
+            r#"<tr><td class="limiter"></td><td><a href="/en/results.html/2007/races/2/malaysia/race-result.html">Malaysia</a></td><td>08 Apr 2007</td><td>Fernando Alonso</td><td>McLaren Mercedes</td><td>56</td><td>1:32:14.930</td><td class="limiter"></td></tr>
+</tbody>"#,
+        );
+    let fastest_laps = std::fs::read_to_string("tests/fixtures/2007/fastest-laps.html")
+        .unwrap()
+        .replace(
+            "<tbody>",
+            r#"<tbody>
+<tr><td class="limiter"></td><td>Malaysia</td><td>Lewis Hamilton</td><td>McLaren Mercedes</td><td>1:36.701</td><td class="limiter"></td></tr>"#,
+        );
+    let server = Server::start_with(
+        "names",
+        &[
+            ("/2007/races.html", &races),
+            ("/2007/fastest-laps.html", &fastest_laps),
+        ],
+    );
+
+    let standings = server.json("/api/f1/2007/driverStandings.json");
+    let leader = &standings["MRData"]["StandingsTable"]["StandingsLists"][0]["DriverStandings"][0];
+    assert_eq!(leader["Driver"]["driverId"], "kimi_raikkonen");
+    assert_eq!(leader["wins"], "1");
+
+    let fastest = server.json("/api/f1/2007/fastest/1/results.json");
+    let races = &fastest["MRData"]["RaceTable"]["Races"];
+    assert_eq!(races[0]["round"], "2");
+    assert_eq!(races[0]["raceName"], "Malaysia Grand Prix");
+    assert_eq!(races[1]["round"], "1");
+    assert_eq!(
+        races[1]["Results"][0]["Driver"]["driverId"],
+        "kimi_raikkonen"
+    );
+}
+
+#[test]
+fn ergast_serves_xml() {
+    let server = Server::start("xml");
+
+    let (status, xml) = server.get("/api/f1/2007/1/results");
+    assert_eq!(status, 200);
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<MRData "));
+    assert!(xml.contains("<RaceTable round=\"1\" season=\"2007\"><Race "));
+    assert!(xml.contains("<RaceName>Australia Grand Prix</RaceName>"));
+    assert!(xml.contains("<ResultsList><Result "));
+    assert!(xml.contains(
+        "<Driver code=\"RAI\" driverId=\"kimi_raikkonen\"><FamilyName>Räikkönen</FamilyName>"
+    ));
+    assert!(xml.contains("<Time>1:25:28.770</Time>"));
+    // like Ergast, XML is the default format
+    assert_eq!(
+        server.get("/api/f1/2007/1/results.xml").1,
+        xml.replace("/2007/1/results\"", "/2007/1/results.xml\"")
+    );
+
+    let (status, body) = server.get("/api/f1/2007/results.csv");
+    assert_eq!(status, 400);
+    assert!(body.contains("only json and xml are supported"));
+}
+
+#[test]
+fn api_routes() {
+    let server = Server::start("api");
+
+    let seasons = server.json("/seasons");
+    assert_eq!(seasons["data"], serde_json::json!([2007]));
+
+    let races = server.json("/seasons/2007/races");
+    assert_eq!(races["total"], 1);
+    assert_eq!(races["data"][0]["grand_prix"], "Australia");
+
+    let results = server.json("/seasons/2007/races/1/results?limit=2&offset=1");
+    assert_eq!(results["total"], 3);
+    assert_eq!(results["limit"], 2);
+    assert_eq!(results["data"][0]["driver"], "Fernando Alonso");
+
+    let results = server.json("/seasons/2007/races/1/results?team=ferrari");
+    assert_eq!(results["total"], 1);
+    assert_eq!(results["data"][0]["driver"], "Kimi Räikkönen");
+
+    let drivers = server.json("/seasons/2007/drivers?driver=alonso");
+    assert_eq!(drivers["total"], 1);
+    let teams = server.json("/seasons/2007/teams");
+    assert_eq!(teams["data"][0]["team"], "Ferrari");
+    let fastest_laps = server.json("/seasons/2007/fastest-laps");
+    assert_eq!(fastest_laps["data"][0]["time"], "1:25.235");
+
+    for (path, status, error) in [
+        ("/seasons/2006/races", 404, "season `2006` not served"),
+        (
+            "/seasons/2007/races/9/results",
+            404,
+            "no race `9` in season `2007`",
+        ),
+        (
+            "/seasons/2007/races/x/results",
+            400,
+            "invalid race index: `x`",
+        ),
+        (
+            "/seasons/2007/races?sort=pos",
+            400,
+            "unknown query parameter: `sort`",
+        ),
+        (
+            "/seasons/2007/podiums",
+            404,
+            "no route: /seasons/2007/podiums",
+        ),
+    ] {
+        let (actual, body) = server.get(path);
+        assert_eq!(actual, status, "{path}: {body}");
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert!(
+            body["error"].as_str().unwrap().contains(error),
+            "{path}: {body}"
+        );
+    }
+}
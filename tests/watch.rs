@@ -0,0 +1,126 @@
+use std::thread;
+
+use f1scraper::diff::{ResultRow, RowChange};
+use f1scraper::scrape::ResultsError;
+use f1scraper::types::{Circuit, RaceResultEntry};
+use f1scraper::watch::{Watch, Webhook};
+
+mod common;
+
+fn row(idx: u16, driver: &str, pos: &str) -> ResultRow {
+    let circuit = common::circuit(idx);
+    let entry = RaceResultEntry {
+        pos: pos.to_string(),
+        driver: driver.to_string(),
+        ..Default::default()
+    };
+    ResultRow::new(2024, circuit, entry)
+}
+
+fn failure(circuit: Option<Circuit>) -> ResultsError {
+    ResultsError {
+        year: 2024,
+        target: "race result".to_string(),
+        circuit,
+        error: anyhow::anyhow!("not published yet"),
+    }
+}
+
+#[test]
+fn reports_published_and_corrected_results() {
+    let mut watch = Watch::new(2024);
+    // the first poll is the baseline
+    assert!(watch.update(vec![row(1, "A", "1")], vec![]).event.is_none());
+    assert!(watch.update(vec![row(1, "A", "1")], vec![]).event.is_none());
+
+    let event = watch
+        .update(vec![row(1, "A", "2"), row(2, "A", "1")], vec![])
+        .event
+        .unwrap();
+    assert_eq!(event.year, 2024);
+    assert_eq!(event.changes.len(), 2);
+    assert!(matches!(&event.changes[0], RowChange::Changed { fields, .. } if fields[0].new == "2"));
+    assert!(matches!(&event.changes[1], RowChange::Added { row } if row.circuit.idx == 2));
+    assert_eq!(watch.rows().map(<[_]>::len), Some(2));
+}
+
+#[test]
+fn keeps_the_rows_of_failed_pages() {
+    let mut watch = Watch::new(2024);
+    watch.update(vec![row(1, "A", "1"), row(2, "A", "1")], vec![]);
+
+    // the second race failed, the first one was corrected
+    let poll = watch.update(
+        vec![row(1, "A", "2")],
+        vec![failure(Some(row(2, "A", "1").circuit))],
+    );
+    assert_eq!(poll.failures.len(), 1);
+    let event = poll.event.unwrap();
+    assert_eq!(event.changes.len(), 1);
+    assert!(matches!(&event.changes[0], RowChange::Changed { circuit, .. } if circuit.idx == 1));
+    assert_eq!(watch.rows().map(<[_]>::len), Some(2));
+
+    // without the summary nothing is compared
+    let poll = watch.update(vec![], vec![failure(None)]);
+    assert!(poll.event.is_none());
+    assert_eq!(watch.rows().map(<[_]>::len), Some(2));
+}
+
+#[test]
+fn races_failing_on_the_first_poll_are_not_reported_as_added() {
+    let mut watch = Watch::new(2024);
+    let second = || Some(row(2, "A", "1").circuit);
+    watch.update(vec![row(1, "A", "1")], vec![failure(second())]);
+
+    // still failing, then scraped: the race joins the baseline silently
+    assert!(watch
+        .update(vec![row(1, "A", "1")], vec![failure(second())])
+        .event
+        .is_none());
+    assert!(watch
+        .update(vec![row(1, "A", "1"), row(2, "A", "1")], vec![])
+        .event
+        .is_none());
+    assert_eq!(watch.rows().map(<[_]>::len), Some(2));
+
+    // and is compared from then on
+    let event = watch
+        .update(vec![row(1, "A", "1"), row(2, "A", "2")], vec![])
+        .event
+        .unwrap();
+    assert_eq!(event.changes.len(), 1);
+    assert!(matches!(&event.changes[0], RowChange::Changed { circuit, .. } if circuit.idx == 2));
+}
+
+#[test]
+fn posts_events_to_the_webhook() {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let handle = thread::spawn(move || {
+        let mut bodies = vec![];
+        for status in [200, 500] {
+            let mut request = server.recv().unwrap();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            bodies.push((request.method().to_string(), body));
+            request.respond(tiny_http::Response::empty(status)).unwrap();
+        }
+        bodies
+    });
+
+    let mut watch = Watch::new(2024);
+    watch.update(vec![], vec![]);
+    let event = watch.update(vec![row(1, "A", "1")], vec![]).event.unwrap();
+    let webhook = Webhook::new(&format!("http://{addr}/events")).unwrap();
+    webhook.send(&event).unwrap();
+    // failing endpoints are reported
+    assert!(webhook.send(&event).is_err());
+
+    let bodies = handle.join().unwrap();
+    let (method, body) = &bodies[0];
+    assert_eq!(method, "POST");
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["year"], 2024);
+    assert_eq!(body["changes"][0]["change"], "added");
+    assert_eq!(body["changes"][0]["row"]["driver"], "A");
+}